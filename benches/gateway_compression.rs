@@ -0,0 +1,129 @@
+//! Compares decoding a gateway session sent as plain json, zlib-stream and zstd-stream.
+//!
+//! The session is synthetic, generated to look like what the gateway sends rather than recorded from it,
+//! so the numbers show how the encodings compare, not what a real session costs.
+//! `decode_session` measures throughput over the json, `decode_session_received` over the bytes received,
+//! which is where the encodings differ in bandwidth.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkGroup, Criterion, Throughput};
+use discord::gateway::compression::{ZlibStreamDecoder, ZstdStreamDecoder};
+use serde_json::Value;
+
+/// The payloads of a session, shaped like what the gateway sends: a READY with many guilds,
+/// then a stream of messages and heartbeat acks.
+fn session_payloads() -> Vec<String> {
+    let mut payloads = vec![common::HELLO.to_string()];
+
+    let guilds: Vec<String> = (0..150u64)
+        .map(|guild| {
+            let channels: Vec<String> = (0..40u64)
+                .map(|channel| format!(
+                    r#"{{"id":"{}","type":0,"name":"channel-{channel}","position":{channel},"parent_id":null,"topic":"The topic of channel {channel}","nsfw":false,"rate_limit_per_user":0,"permission_overwrites":[]}}"#,
+                    1100000000000000000 + guild * 1000 + channel
+                ))
+                .collect();
+            format!(
+                r#"{{"id":"{}","name":"Guild {guild}","icon":null,"owner_id":"80351110224678912","features":["COMMUNITY","NEWS"],"channels":[{}]}}"#,
+                1000000000000000000 + guild,
+                channels.join(",")
+            )
+        })
+        .collect();
+    payloads.push(format!(
+        r#"{{"t":"READY","s":1,"op":0,"d":{{"v":9,"session_id":"2f1b2c51e5a3dc8d0c8ae4a4f3d0e1ab","resume_gateway_url":"wss://gateway-us-east1-b.discord.gg","guilds":[{}]}}}}"#,
+        guilds.join(",")
+    ));
+
+    for i in 0..500u64 {
+        payloads.push(format!(
+            r#"{{"t":"MESSAGE_CREATE","s":{},"op":0,"d":{{"id":"{}","channel_id":"{}","guild_id":"{}","type":0,"content":"Message number {i} in the benchmark session","author":{{"id":"{}","username":"user{}","avatar":null,"discriminator":"0","public_flags":0}},"embeds":[],"attachments":[],"mentions":[],"mention_roles":[],"pinned":false,"tts":false,"timestamp":"2024-06-01T12:00:00.000000+00:00"}}}}"#,
+            i + 2,
+            1200000000000000000 + i,
+            1100000000000000000 + (i % 40),
+            1000000000000000000 + (i % 150),
+            900000000000000000 + (i % 25),
+            i % 25
+        ));
+        if i % 50 == 0 {
+            payloads.push(common::HEARTBEAT_ACK.to_string());
+        }
+    }
+
+    payloads
+}
+
+fn total_len<T: AsRef<[u8]>>(messages: &[T]) -> u64 {
+    messages.iter().map(|message| message.as_ref().len() as u64).sum()
+}
+
+fn bench_json(group: &mut BenchmarkGroup<WallTime>, payloads: &[String]) {
+    group.bench_function("json", |b| {
+        b.iter(|| {
+            for payload in payloads {
+                serde_json::from_str::<Value>(payload).unwrap();
+            }
+        })
+    });
+}
+
+fn bench_zlib(group: &mut BenchmarkGroup<WallTime>, messages: &[Vec<u8>]) {
+    group.bench_function("zlib-stream", |b| {
+        b.iter_batched_ref(
+            ZlibStreamDecoder::new,
+            |decoder| {
+                for message in messages {
+                    let payload = decoder.push(message).unwrap().unwrap();
+                    serde_json::from_str::<Value>(&payload).unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_zstd(group: &mut BenchmarkGroup<WallTime>, messages: &[Vec<u8>]) {
+    group.bench_function("zstd-stream", |b| {
+        b.iter_batched_ref(
+            || ZstdStreamDecoder::new().unwrap(),
+            |decoder| {
+                for message in messages {
+                    let payload = decoder.push(message).unwrap();
+                    serde_json::from_str::<Value>(&payload).unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn decode_session(c: &mut Criterion) {
+    let payloads = session_payloads();
+    let payload_strs: Vec<&str> = payloads.iter().map(String::as_str).collect();
+    let zlib = common::zlib_stream(&payload_strs);
+    let zstd = common::zstd_stream(&payload_strs, 20);
+
+    let mut group = c.benchmark_group("decode_session");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(total_len(&payloads)));
+    bench_json(&mut group, &payloads);
+    bench_zlib(&mut group, &zlib);
+    bench_zstd(&mut group, &zstd);
+    group.finish();
+
+    let mut group = c.benchmark_group("decode_session_received");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(total_len(&payloads)));
+    bench_json(&mut group, &payloads);
+    group.throughput(Throughput::Bytes(total_len(&zlib)));
+    bench_zlib(&mut group, &zlib);
+    group.throughput(Throughput::Bytes(total_len(&zstd)));
+    bench_zstd(&mut group, &zstd);
+    group.finish();
+}
+
+criterion_group!(benches, decode_session);
+criterion_main!(benches);
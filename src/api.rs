@@ -1,34 +1,37 @@
-use reqwest::{Client, Method};
-use serde::Deserialize;
-use serde::Serialize;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
 use serde_json::json;
-use thiserror::Error;
-use crate::model::channel::Channel;
-use crate::model::guild::Guild;
+use time::{Duration, OffsetDateTime};
+use crate::builders::attachment::multipart_fields_form;
+use crate::builders::emoji::{CreateEmoji, EditEmoji};
+use crate::builders::message::{CreateMessage, EditMessage};
+use crate::builders::sticker::{CreateSticker, EditSticker};
+use crate::builders::forum::{CreateForumPost, CreateForumTag};
+use crate::builders::thread::CreateThread;
+use crate::builders::webhook::{CreateWebhook, ExecuteWebhook, ModifyWebhook};
+use crate::model::channel::{Channel, ThreadList};
+use crate::builders::guild::ModifyGuild;
+use crate::builders::role::{EditRole, RolePosition};
+use crate::builders::scheduled_event::{CreateScheduledEvent, ModifyScheduledEvent};
+use crate::builders::invite::CreateInvite;
+use crate::builders::member::ModifyMember;
+use crate::model::guild::audit_log::{AuditLog, EventType};
+use crate::model::guild::{Ban, BulkBan, Guild, GuildMemberData, GuildPreview, Role};
+use crate::model::invite::Invite;
 use crate::model::message::DefaultMessageData;
 use crate::model::message::Message;
+use crate::model::message::{Emoji, MessagePins, ReactionEmoji, ReactionType, Sticker};
+use crate::model::scheduled_event::{ScheduledEvent, ScheduledEventUser};
 use crate::model::user::MainUserData;
 use crate::model::user::UserData;
+use crate::model::webhook::Webhook;
 
 use crate::model::Snowflake;
-use crate::{endpoints, http::{self, QueryError}};
+use crate::{endpoints, http::{self, HttpClient, QueryError}};
 pub type Result<T> = core::result::Result<T, QueryError>;
 
-#[derive(Error, Debug)]
-pub enum DiscordError {
-    #[error("Ratelimit reached: try again after {retry_after} seconds")]
-    RateLimitReached { retry_after: f64 },
-    #[error("Authentication failed")]
-    AuthenticationFail,
-    #[error("Error {code}: {message}")]
-    Other {
-        message: String,
-        code: u64
-    }
-}
-
 pub(crate) async fn get_authenticated_user_data(
-    client: Client
+    client: HttpClient
 ) -> Result<MainUserData> {
     http::get_struct(
         client,
@@ -38,7 +41,7 @@ pub(crate) async fn get_authenticated_user_data(
 }
 
 pub(crate) async fn get_user_from_id(
-    client: Client,
+    client: HttpClient,
     id: &Snowflake,
 ) -> Result<UserData> {
     http::get_struct(
@@ -49,66 +52,153 @@ pub(crate) async fn get_user_from_id(
 }
 
 pub(crate) async fn get_private_channels(
-    client: Client
+    client: HttpClient
 ) -> Result<Vec<Channel>> {
-    Ok(
-        http::get_struct::<Vec<Channel>>(client, &endpoints::PRIVATE_CHANNELS, Method::GET).await?
-    )
+    http::get_struct::<Vec<Channel>>(client, endpoints::PRIVATE_CHANNELS, Method::GET).await
 }
 
 pub(crate) async fn get_guilds(
-    client: Client
+    client: HttpClient
 ) -> Result<Vec<Guild>> {
-    Ok(
-        http::get_struct::<Vec<Guild>>(client, &endpoints::GUILDS, Method::GET).await?
-    )
+    http::get_struct::<Vec<Guild>>(client, endpoints::GUILDS, Method::GET).await
 }
 
-pub(crate) async fn get_channels_in_guild(
-    client: Client,
+pub(crate) async fn get_guild(
+    client: HttpClient,
     guild_id: &Snowflake,
-) -> Result<Vec<Channel>> {
-    http::get_struct::<Vec<Channel>>(client, &endpoints::guild_channels(guild_id), Method::GET).await
+    with_counts: bool
+) -> Result<Guild> {
+    http::get_struct(client, &endpoints::guild(guild_id, with_counts), Method::GET).await
 }
 
-// messaging utilities
-#[derive(Serialize)]
-struct MessagePostData {
-    content: String
+pub(crate) async fn get_guild_preview(
+    client: HttpClient,
+    guild_id: &Snowflake
+) -> Result<GuildPreview> {
+    http::get_struct(client, &endpoints::guild_preview(guild_id), Method::GET).await
 }
 
-impl MessagePostData {
-    fn new(content: String) -> MessagePostData {
-        MessagePostData {
-            content
-        }
-    }
+pub(crate) async fn modify_guild(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    guild: &ModifyGuild,
+    reason: Option<&str>
+) -> Result<Guild> {
+    http::get_struct_body_with_reason(client, &endpoints::modify_guild(guild_id), guild, Method::PATCH, reason).await
 }
 
+pub(crate) async fn get_channels_in_guild(
+    client: HttpClient,
+    guild_id: &Snowflake,
+) -> Result<Vec<Channel>> {
+    http::get_struct::<Vec<Channel>>(client, &endpoints::guild_channels(guild_id), Method::GET).await
+}
+
+// messaging utilities
 pub async fn start_typing(
-    client: Client,
+    client: HttpClient,
     channel_id: &Snowflake
 ) -> Result<()> {
-    http::send(client, &endpoints::start_typing(&channel_id), Method::POST)
+    http::send(client, &endpoints::start_typing(channel_id), Method::POST)
         .await
 }
 
 pub async fn send_message(
-    client: Client,
+    client: HttpClient,
     channel_id: &Snowflake,
-    content: &str
+    message: CreateMessage
 ) -> Result<DefaultMessageData> {
-    let post_data = MessagePostData::new(content.to_string());
-    http::get_struct_body(
-        client, 
-        &endpoints::send_message(channel_id), 
-        &post_data, 
+    let (payload, files) = message.into_payload();
+    let route = endpoints::send_message(channel_id);
+    if files.is_empty() {
+        http::get_struct_body(client, &route, &payload, Method::POST).await
+    } else {
+        http::get_struct_multipart(client, &route, &payload, files, Method::POST).await
+    }
+}
+
+pub async fn edit_message(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    message: EditMessage
+) -> Result<Message> {
+    let (payload, files) = message.into_payload();
+    let route = endpoints::message(channel_id, message_id);
+    if files.is_empty() {
+        http::get_struct_body(client, &route, &payload, Method::PATCH).await
+    } else {
+        http::get_struct_multipart(client, &route, &payload, files, Method::PATCH).await
+    }
+}
+
+pub async fn delete_message(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(
+        client,
+        &endpoints::message(channel_id, message_id),
+        Method::DELETE,
+        reason
+    ).await
+}
+
+/// Discord refuses to bulk delete messages older than this.
+const BULK_DELETE_MAX_AGE: Duration = Duration::days(14);
+
+pub async fn bulk_delete_messages(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_ids: &[Snowflake],
+    reason: Option<&str>
+) -> Result<()> {
+    if !(2..=100).contains(&message_ids.len()) {
+        return Err(QueryError::InvalidRequest {
+            error: format!("Between 2 and 100 messages can be bulk deleted, got {}", message_ids.len())
+        });
+    }
+    let oldest_allowed = OffsetDateTime::now_utc() - BULK_DELETE_MAX_AGE;
+    for id in message_ids {
+        match id.timestamp() {
+            Some(created_at) if created_at > oldest_allowed => {},
+            Some(_) => return Err(QueryError::InvalidRequest {
+                error: format!("Message {id} is older than 14 days and can't be bulk deleted")
+            }),
+            None => return Err(QueryError::InvalidRequest {
+                error: format!("{id} is not a valid message id")
+            }),
+        }
+    }
+
+    let body = json!({
+        "messages": message_ids
+    });
+    http::send_body_with_reason(
+        client,
+        &endpoints::bulk_delete_messages(channel_id),
+        &body,
+        Method::POST,
+        reason
+    ).await
+}
+
+pub async fn crosspost_message(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake
+) -> Result<Message> {
+    http::get_struct(
+        client,
+        &endpoints::crosspost_message(channel_id, message_id),
         Method::POST
     ).await
 }
 
 pub async fn messages(
-    client: Client,
+    client: HttpClient,
     channel_id: &Snowflake,
     before_message_id: Option<&Snowflake>,
     limit: u8
@@ -121,29 +211,29 @@ pub async fn messages(
 }
 
 pub async fn message_from_id(
-    client: Client,
+    client: HttpClient,
     channel_id: &Snowflake,
     message_id: &Snowflake
 ) -> Result<Message> {
     http::get_struct(
         client, 
-        &endpoints::message(&channel_id, message_id), 
+        &endpoints::message(channel_id, message_id), 
         Method::GET
     ).await
 }
 
 pub async fn open_channel(
-    client: Client,
+    client: HttpClient,
     recipient_ids: &[Snowflake]
 ) -> Result<Channel> {
     let body = json!({
         "recipients": recipient_ids
     });
-    http::get_struct_body(client, &endpoints::PRIVATE_CHANNELS, &body, Method::POST).await
+    http::get_struct_body(client, endpoints::PRIVATE_CHANNELS, &body, Method::POST).await
 }
 
 pub async fn close_channel(
-    client: Client,
+    client: HttpClient,
     channel_id: &Snowflake
 ) -> Result<Channel> {
     http::get_struct(
@@ -152,4 +242,754 @@ pub async fn close_channel(
         &format!("{}?silent=false", endpoints::channel(channel_id)), 
         Method::DELETE
     ).await
-}
\ No newline at end of file
+}
+pub async fn add_reaction(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    emoji: &ReactionEmoji
+) -> Result<()> {
+    http::send(
+        client,
+        &endpoints::user_reaction(channel_id, message_id, emoji, None),
+        Method::PUT
+    ).await
+}
+
+/// `user_id` is None to remove the current user's reaction.
+pub async fn remove_reaction(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    emoji: &ReactionEmoji,
+    user_id: Option<&Snowflake>
+) -> Result<()> {
+    http::send(
+        client,
+        &endpoints::user_reaction(channel_id, message_id, emoji, user_id),
+        Method::DELETE
+    ).await
+}
+
+pub async fn remove_all_reactions(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake
+) -> Result<()> {
+    http::send(client, &endpoints::reactions(channel_id, message_id), Method::DELETE).await
+}
+
+pub async fn remove_emoji_reactions(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    emoji: &ReactionEmoji
+) -> Result<()> {
+    http::send(
+        client,
+        &endpoints::emoji_reactions(channel_id, message_id, emoji),
+        Method::DELETE
+    ).await
+}
+
+pub async fn reactors(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    emoji: &ReactionEmoji,
+    reaction_type: ReactionType,
+    after_user_id: Option<&Snowflake>,
+    limit: u8
+) -> Result<Vec<UserData>> {
+    http::get_struct(
+        client,
+        &endpoints::reactors(channel_id, message_id, emoji, reaction_type, after_user_id, limit),
+        Method::GET
+    ).await
+}
+
+/// Lists up to 50 pinned messages, use `pins` to page through all of them.
+pub async fn pinned_messages(
+    client: HttpClient,
+    channel_id: &Snowflake
+) -> Result<Vec<Message>> {
+    http::get_struct(client, &endpoints::pinned_messages(channel_id), Method::GET).await
+}
+
+pub async fn pins(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    before: Option<OffsetDateTime>,
+    limit: u8
+) -> Result<MessagePins> {
+    http::get_struct(client, &endpoints::pins(channel_id, before, limit), Method::GET).await
+}
+
+pub async fn pin_message(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::pin(channel_id, message_id), Method::PUT, reason).await
+}
+
+pub async fn unpin_message(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::pin(channel_id, message_id), Method::DELETE, reason).await
+}
+
+pub async fn start_thread_from_message(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    message_id: &Snowflake,
+    thread: &CreateThread,
+    reason: Option<&str>
+) -> Result<Channel> {
+    http::get_struct_body_with_reason(
+        client,
+        &endpoints::message_threads(channel_id, message_id),
+        thread,
+        Method::POST,
+        reason
+    ).await
+}
+
+pub async fn start_thread(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    thread: &CreateThread,
+    reason: Option<&str>
+) -> Result<Channel> {
+    http::get_struct_body_with_reason(client, &endpoints::threads(channel_id), thread, Method::POST, reason).await
+}
+
+/// `user_id` is None to add the current user.
+pub async fn add_thread_member(
+    client: HttpClient,
+    thread_id: &Snowflake,
+    user_id: Option<&Snowflake>
+) -> Result<()> {
+    http::send(client, &endpoints::thread_member(thread_id, user_id), Method::PUT).await
+}
+
+/// `user_id` is None to remove the current user.
+pub async fn remove_thread_member(
+    client: HttpClient,
+    thread_id: &Snowflake,
+    user_id: Option<&Snowflake>
+) -> Result<()> {
+    http::send(client, &endpoints::thread_member(thread_id, user_id), Method::DELETE).await
+}
+
+pub async fn public_archived_threads(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    before: Option<OffsetDateTime>,
+    limit: u8
+) -> Result<ThreadList> {
+    http::get_struct(
+        client,
+        &endpoints::public_archived_threads(channel_id, before, limit),
+        Method::GET
+    ).await
+}
+
+pub async fn private_archived_threads(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    before: Option<OffsetDateTime>,
+    limit: u8
+) -> Result<ThreadList> {
+    http::get_struct(
+        client,
+        &endpoints::private_archived_threads(channel_id, before, limit),
+        Method::GET
+    ).await
+}
+
+pub async fn joined_private_archived_threads(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    before_thread_id: Option<&Snowflake>,
+    limit: u8
+) -> Result<ThreadList> {
+    http::get_struct(
+        client,
+        &endpoints::joined_private_archived_threads(channel_id, before_thread_id, limit),
+        Method::GET
+    ).await
+}
+
+pub async fn active_threads(
+    client: HttpClient,
+    guild_id: &Snowflake
+) -> Result<ThreadList> {
+    http::get_struct(client, &endpoints::active_threads(guild_id), Method::GET).await
+}
+
+pub async fn create_forum_post(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    post: CreateForumPost,
+    reason: Option<&str>
+) -> Result<Channel> {
+    let (payload, files) = post.into_payload();
+    let route = endpoints::threads(channel_id);
+    if files.is_empty() {
+        http::get_struct_body_with_reason(client, &route, &payload, Method::POST, reason).await
+    } else {
+        http::get_struct_multipart_with_reason(client, &route, &payload, files, Method::POST, reason).await
+    }
+}
+
+pub async fn set_applied_tags(
+    client: HttpClient,
+    thread_id: &Snowflake,
+    tag_ids: &[Snowflake],
+    reason: Option<&str>
+) -> Result<Channel> {
+    let body = json!({
+        "applied_tags": tag_ids
+    });
+    http::get_struct_body_with_reason(client, &endpoints::channel(thread_id), &body, Method::PATCH, reason).await
+}
+
+pub async fn set_available_tags(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    tags: &[CreateForumTag],
+    reason: Option<&str>
+) -> Result<Channel> {
+    let body = json!({
+        "available_tags": tags
+    });
+    http::get_struct_body_with_reason(client, &endpoints::channel(channel_id), &body, Method::PATCH, reason).await
+}
+
+pub async fn roles(
+    client: HttpClient,
+    guild_id: &Snowflake
+) -> Result<Vec<Role>> {
+    http::get_struct(client, &endpoints::roles(guild_id), Method::GET).await
+}
+
+pub async fn role(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    role_id: &Snowflake
+) -> Result<Role> {
+    http::get_struct(client, &endpoints::role(guild_id, role_id), Method::GET).await
+}
+
+pub async fn create_role(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    role: &EditRole,
+    reason: Option<&str>
+) -> Result<Role> {
+    http::get_struct_body_with_reason(client, &endpoints::roles(guild_id), role, Method::POST, reason).await
+}
+
+pub async fn modify_role(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    role_id: &Snowflake,
+    role: &EditRole,
+    reason: Option<&str>
+) -> Result<Role> {
+    http::get_struct_body_with_reason(client, &endpoints::role(guild_id, role_id), role, Method::PATCH, reason).await
+}
+
+pub async fn reorder_roles(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    positions: &[RolePosition],
+    reason: Option<&str>
+) -> Result<Vec<Role>> {
+    http::get_struct_body_with_reason(client, &endpoints::roles(guild_id), &positions, Method::PATCH, reason).await
+}
+
+pub async fn delete_role(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    role_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::role(guild_id, role_id), Method::DELETE, reason).await
+}
+
+pub async fn add_member_role(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake,
+    role_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::member_role(guild_id, user_id, role_id), Method::PUT, reason).await
+}
+
+pub async fn remove_member_role(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake,
+    role_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::member_role(guild_id, user_id, role_id), Method::DELETE, reason).await
+}
+
+pub async fn members(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    after_user_id: Option<&Snowflake>,
+    limit: u16
+) -> Result<Vec<GuildMemberData>> {
+    http::get_struct(client, &endpoints::members(guild_id, after_user_id, limit), Method::GET).await
+}
+
+pub async fn search_members(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    query: &str,
+    limit: u16
+) -> Result<Vec<GuildMemberData>> {
+    http::get_struct(client, &endpoints::search_members(guild_id, query, limit), Method::GET).await
+}
+
+pub async fn member(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake
+) -> Result<GuildMemberData> {
+    http::get_struct(client, &endpoints::member(guild_id, user_id), Method::GET).await
+}
+
+pub async fn modify_member(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake,
+    member: &ModifyMember,
+    reason: Option<&str>
+) -> Result<GuildMemberData> {
+    http::get_struct_body_with_reason(client, &endpoints::member(guild_id, user_id), member, Method::PATCH, reason).await
+}
+
+pub async fn kick_member(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::member(guild_id, user_id), Method::DELETE, reason).await
+}
+
+/// Discord deletes at most a week of messages when banning.
+const MAX_DELETE_MESSAGE_SECONDS: u32 = 604800;
+
+fn check_delete_message_seconds(delete_message_seconds: u32) -> Result<()> {
+    if delete_message_seconds > MAX_DELETE_MESSAGE_SECONDS {
+        return Err(QueryError::InvalidRequest {
+            error: format!("At most {MAX_DELETE_MESSAGE_SECONDS} seconds of messages can be deleted, got {delete_message_seconds}")
+        });
+    }
+    Ok(())
+}
+
+pub async fn ban(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake,
+    delete_message_seconds: u32,
+    reason: Option<&str>
+) -> Result<()> {
+    check_delete_message_seconds(delete_message_seconds)?;
+    let body = json!({
+        "delete_message_seconds": delete_message_seconds
+    });
+    http::send_body_with_reason(client, &endpoints::ban(guild_id, user_id), &body, Method::PUT, reason).await
+}
+
+pub async fn bulk_ban(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_ids: &[Snowflake],
+    delete_message_seconds: u32,
+    reason: Option<&str>
+) -> Result<BulkBan> {
+    if !(1..=200).contains(&user_ids.len()) {
+        return Err(QueryError::InvalidRequest {
+            error: format!("Between 1 and 200 users can be bulk banned, got {}", user_ids.len())
+        });
+    }
+    check_delete_message_seconds(delete_message_seconds)?;
+    let body = json!({
+        "user_ids": user_ids,
+        "delete_message_seconds": delete_message_seconds
+    });
+    http::get_struct_body_with_reason(client, &endpoints::bulk_ban(guild_id), &body, Method::POST, reason).await
+}
+
+pub async fn unban(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::ban(guild_id, user_id), Method::DELETE, reason).await
+}
+
+pub async fn bans(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    after_user_id: Option<&Snowflake>,
+    limit: u16
+) -> Result<Vec<Ban>> {
+    http::get_struct(client, &endpoints::bans(guild_id, after_user_id, limit), Method::GET).await
+}
+
+pub async fn get_ban(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: &Snowflake
+) -> Result<Ban> {
+    http::get_struct(client, &endpoints::ban(guild_id, user_id), Method::GET).await
+}
+
+pub async fn audit_log(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    user_id: Option<&Snowflake>,
+    action_type: Option<EventType>,
+    before: Option<&Snowflake>,
+    after: Option<&Snowflake>,
+    limit: u8
+) -> Result<AuditLog> {
+    http::get_struct(
+        client,
+        &endpoints::audit_log(guild_id, user_id, action_type, before, after, limit),
+        Method::GET
+    ).await
+}
+
+pub async fn create_invite(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    invite: &CreateInvite,
+    reason: Option<&str>
+) -> Result<Invite> {
+    http::get_struct_body_with_reason(client, &endpoints::channel_invites(channel_id), invite, Method::POST, reason).await
+}
+
+pub async fn resolve_invite(
+    client: HttpClient,
+    code: &str,
+    with_counts: bool
+) -> Result<Invite> {
+    http::get_struct(client, &endpoints::resolve_invite(code, with_counts), Method::GET).await
+}
+
+pub async fn channel_invites(
+    client: HttpClient,
+    channel_id: &Snowflake
+) -> Result<Vec<Invite>> {
+    http::get_struct(client, &endpoints::channel_invites(channel_id), Method::GET).await
+}
+
+pub async fn guild_invites(
+    client: HttpClient,
+    guild_id: &Snowflake
+) -> Result<Vec<Invite>> {
+    http::get_struct(client, &endpoints::guild_invites(guild_id), Method::GET).await
+}
+
+pub async fn delete_invite(
+    client: HttpClient,
+    code: &str,
+    reason: Option<&str>
+) -> Result<Invite> {
+    http::get_struct_with_reason(client, &endpoints::invite(code), Method::DELETE, reason).await
+}
+
+pub async fn accept_invite(
+    client: HttpClient,
+    code: &str
+) -> Result<Invite> {
+    http::get_struct_body(client, &endpoints::invite(code), &json!({}), Method::POST).await
+}
+
+pub async fn create_webhook(
+    client: HttpClient,
+    channel_id: &Snowflake,
+    webhook: &CreateWebhook,
+    reason: Option<&str>
+) -> Result<Webhook> {
+    http::get_struct_body_with_reason(client, &endpoints::channel_webhooks(channel_id), webhook, Method::POST, reason).await
+}
+
+pub async fn channel_webhooks(
+    client: HttpClient,
+    channel_id: &Snowflake
+) -> Result<Vec<Webhook>> {
+    http::get_struct(client, &endpoints::channel_webhooks(channel_id), Method::GET).await
+}
+
+pub async fn guild_webhooks(
+    client: HttpClient,
+    guild_id: &Snowflake
+) -> Result<Vec<Webhook>> {
+    http::get_struct(client, &endpoints::guild_webhooks(guild_id), Method::GET).await
+}
+
+pub async fn get_webhook(
+    client: HttpClient,
+    webhook_id: &Snowflake
+) -> Result<Webhook> {
+    http::get_struct(client, &endpoints::webhook(webhook_id), Method::GET).await
+}
+
+pub async fn modify_webhook(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    webhook: &ModifyWebhook,
+    reason: Option<&str>
+) -> Result<Webhook> {
+    http::get_struct_body_with_reason(client, &endpoints::webhook(webhook_id), webhook, Method::PATCH, reason).await
+}
+
+pub async fn delete_webhook(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::webhook(webhook_id), Method::DELETE, reason).await
+}
+
+pub async fn get_webhook_with_token(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    token: &str
+) -> Result<Webhook> {
+    http::get_struct(client, &endpoints::webhook_with_token(webhook_id, token), Method::GET).await
+}
+
+pub async fn modify_webhook_with_token(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    token: &str,
+    webhook: &ModifyWebhook,
+    reason: Option<&str>
+) -> Result<Webhook> {
+    http::get_struct_body_with_reason(client, &endpoints::webhook_with_token(webhook_id, token), webhook, Method::PATCH, reason).await
+}
+
+pub async fn delete_webhook_with_token(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    token: &str,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::webhook_with_token(webhook_id, token), Method::DELETE, reason).await
+}
+
+/// With `wait`, discord only answers once the message is sent, with the message.
+/// Without it, nothing is returned.
+pub async fn execute_webhook<T: DeserializeOwned>(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    token: &str,
+    message: ExecuteWebhook,
+    wait: bool
+) -> Result<T> {
+    let route = endpoints::execute_webhook(webhook_id, token, wait, message.thread_id());
+    let (payload, files) = message.into_payload();
+    if files.is_empty() {
+        http::get_struct_body(client, &route, &payload, Method::POST).await
+    } else {
+        http::get_struct_multipart(client, &route, &payload, files, Method::POST).await
+    }
+}
+
+pub async fn get_webhook_message(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    token: &str,
+    message_id: &Snowflake,
+    thread_id: Option<&Snowflake>
+) -> Result<Message> {
+    http::get_struct(client, &endpoints::webhook_message(webhook_id, token, message_id, thread_id), Method::GET).await
+}
+
+pub async fn edit_webhook_message(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    token: &str,
+    message_id: &Snowflake,
+    message: EditMessage,
+    thread_id: Option<&Snowflake>
+) -> Result<Message> {
+    let (payload, files) = message.into_payload();
+    let route = endpoints::webhook_message(webhook_id, token, message_id, thread_id);
+    if files.is_empty() {
+        http::get_struct_body(client, &route, &payload, Method::PATCH).await
+    } else {
+        http::get_struct_multipart(client, &route, &payload, files, Method::PATCH).await
+    }
+}
+
+pub async fn delete_webhook_message(
+    client: HttpClient,
+    webhook_id: &Snowflake,
+    token: &str,
+    message_id: &Snowflake,
+    thread_id: Option<&Snowflake>
+) -> Result<()> {
+    http::send(client, &endpoints::webhook_message(webhook_id, token, message_id, thread_id), Method::DELETE).await
+}
+
+pub async fn guild_emojis(
+    client: HttpClient,
+    guild_id: &Snowflake
+) -> Result<Vec<Emoji>> {
+    http::get_struct(client, &endpoints::guild_emojis(guild_id), Method::GET).await
+}
+
+pub async fn guild_emoji(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    emoji_id: &Snowflake
+) -> Result<Emoji> {
+    http::get_struct(client, &endpoints::guild_emoji(guild_id, emoji_id), Method::GET).await
+}
+
+pub async fn create_emoji(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    emoji: &CreateEmoji,
+    reason: Option<&str>
+) -> Result<Emoji> {
+    http::get_struct_body_with_reason(client, &endpoints::guild_emojis(guild_id), emoji, Method::POST, reason).await
+}
+
+pub async fn modify_emoji(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    emoji_id: &Snowflake,
+    emoji: &EditEmoji,
+    reason: Option<&str>
+) -> Result<Emoji> {
+    http::get_struct_body_with_reason(client, &endpoints::guild_emoji(guild_id, emoji_id), emoji, Method::PATCH, reason).await
+}
+
+pub async fn delete_emoji(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    emoji_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::guild_emoji(guild_id, emoji_id), Method::DELETE, reason).await
+}
+
+pub async fn guild_stickers(
+    client: HttpClient,
+    guild_id: &Snowflake
+) -> Result<Vec<Sticker>> {
+    http::get_struct(client, &endpoints::guild_stickers(guild_id), Method::GET).await
+}
+
+pub async fn guild_sticker(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    sticker_id: &Snowflake
+) -> Result<Sticker> {
+    http::get_struct(client, &endpoints::guild_sticker(guild_id, sticker_id), Method::GET).await
+}
+
+pub async fn create_sticker(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    sticker: CreateSticker,
+    reason: Option<&str>
+) -> Result<Sticker> {
+    let (fields, file) = sticker.into_fields();
+    let form = multipart_fields_form(fields, "file", file).await?;
+    http::get_struct_form_with_reason(client, &endpoints::guild_stickers(guild_id), form, Method::POST, reason).await
+}
+
+pub async fn modify_sticker(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    sticker_id: &Snowflake,
+    sticker: &EditSticker,
+    reason: Option<&str>
+) -> Result<Sticker> {
+    http::get_struct_body_with_reason(client, &endpoints::guild_sticker(guild_id, sticker_id), sticker, Method::PATCH, reason).await
+}
+
+pub async fn delete_sticker(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    sticker_id: &Snowflake,
+    reason: Option<&str>
+) -> Result<()> {
+    http::send_with_reason(client, &endpoints::guild_sticker(guild_id, sticker_id), Method::DELETE, reason).await
+}
+
+pub async fn scheduled_events(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    with_user_count: bool
+) -> Result<Vec<ScheduledEvent>> {
+    http::get_struct(client, &endpoints::list_scheduled_events(guild_id, with_user_count), Method::GET).await
+}
+
+pub async fn scheduled_event(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    event_id: &Snowflake,
+    with_user_count: bool
+) -> Result<ScheduledEvent> {
+    http::get_struct(client, &endpoints::get_scheduled_event(guild_id, event_id, with_user_count), Method::GET).await
+}
+
+pub async fn create_scheduled_event(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    event: &CreateScheduledEvent,
+    reason: Option<&str>
+) -> Result<ScheduledEvent> {
+    http::get_struct_body_with_reason(client, &endpoints::scheduled_events(guild_id), event, Method::POST, reason).await
+}
+
+pub async fn modify_scheduled_event(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    event_id: &Snowflake,
+    event: &ModifyScheduledEvent,
+    reason: Option<&str>
+) -> Result<ScheduledEvent> {
+    http::get_struct_body_with_reason(client, &endpoints::scheduled_event(guild_id, event_id), event, Method::PATCH, reason).await
+}
+
+pub async fn delete_scheduled_event(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    event_id: &Snowflake
+) -> Result<()> {
+    http::send(client, &endpoints::scheduled_event(guild_id, event_id), Method::DELETE).await
+}
+
+pub async fn scheduled_event_users(
+    client: HttpClient,
+    guild_id: &Snowflake,
+    event_id: &Snowflake,
+    after: Option<&Snowflake>,
+    limit: u8,
+    with_member: bool
+) -> Result<Vec<ScheduledEventUser>> {
+    http::get_struct(client, &endpoints::scheduled_event_users(guild_id, event_id, after, limit, with_member), Method::GET).await
+}
@@ -0,0 +1,162 @@
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use reqwest::multipart::{Form, Part};
+use reqwest::Body;
+use serde::Serialize;
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+use crate::http::QueryError;
+
+enum AttachmentSource {
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+    Reader(Pin<Box<dyn AsyncRead + Send + Sync>>),
+}
+
+/// A file to upload along with a message.
+pub struct Attachment {
+    filename: String,
+    description: Option<String>,
+    spoiler: bool,
+    source: AttachmentSource,
+}
+
+impl Debug for Attachment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Attachment")
+            .field("filename", &self.filename)
+            .field("description", &self.description)
+            .field("spoiler", &self.spoiler)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Attachment {
+    /// The file is read when the message is sent.
+    /// The attachment is named after the file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Attachment {
+        let path = path.as_ref();
+        Attachment {
+            filename: path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            description: None,
+            spoiler: false,
+            source: AttachmentSource::Path(path.to_path_buf()),
+        }
+    }
+
+    pub fn from_bytes<S: AsRef<str>>(filename: S, bytes: Vec<u8>) -> Attachment {
+        Attachment {
+            filename: filename.as_ref().to_string(),
+            description: None,
+            spoiler: false,
+            source: AttachmentSource::Bytes(bytes),
+        }
+    }
+
+    /// The reader is streamed to discord as the message is sent.
+    pub fn from_reader<S, R>(filename: S, reader: R) -> Attachment
+    where
+        S: AsRef<str>,
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        Attachment {
+            filename: filename.as_ref().to_string(),
+            description: None,
+            spoiler: false,
+            source: AttachmentSource::Reader(Box::pin(reader)),
+        }
+    }
+
+    /// Overrides the name the file is uploaded with.
+    pub fn filename<S: AsRef<str>>(mut self, filename: S) -> Attachment {
+        self.filename = filename.as_ref().to_string();
+        self
+    }
+
+    /// Alt text for the file.
+    pub fn description<S: AsRef<str>>(mut self, description: S) -> Attachment {
+        self.description = Some(description.as_ref().to_string());
+        self
+    }
+
+    /// Hides the file behind a spoiler.
+    pub fn spoiler(mut self, spoiler: bool) -> Attachment {
+        self.spoiler = spoiler;
+        self
+    }
+
+    fn upload_filename(&self) -> String {
+        if self.spoiler && !self.filename.starts_with("SPOILER_") {
+            format!("SPOILER_{}", self.filename)
+        } else {
+            self.filename.clone()
+        }
+    }
+
+    pub(crate) fn payload(&self, id: usize) -> AttachmentPayload {
+        AttachmentPayload {
+            id,
+            filename: self.upload_filename(),
+            description: self.description.clone(),
+        }
+    }
+
+    async fn into_part(self) -> std::io::Result<Part> {
+        let filename = self.upload_filename();
+        let part = match self.source {
+            AttachmentSource::Path(path) => {
+                let file = tokio::fs::File::open(&path).await?;
+                let len = file.metadata().await?.len();
+                Part::stream_with_length(Body::wrap_stream(ReaderStream::new(file)), len)
+            },
+            AttachmentSource::Bytes(bytes) => Part::bytes(bytes),
+            AttachmentSource::Reader(reader) => Part::stream(Body::wrap_stream(ReaderStream::new(reader))),
+        };
+
+        Ok(part.file_name(filename))
+    }
+}
+
+/// How an attachment is described in a message's `payload_json`.
+#[derive(Serialize, Debug)]
+pub(crate) struct AttachmentPayload {
+    id: usize,
+    filename: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// Builds a `multipart/form-data` body out of a json payload and its files.
+/// The payload should describe the files in its `attachments` field, see `Attachment::payload`.
+pub(crate) async fn multipart_form<S: Serialize>(payload: &S, attachments: Vec<Attachment>) -> Result<Form, QueryError> {
+    let payload_json = serde_json::to_string(payload)
+        .map_err(|e| QueryError::Other { error: format!("Failed to serialize payload_json: {e}") })?;
+    let mut form = Form::new().part(
+        "payload_json",
+        Part::text(payload_json).mime_str("application/json")
+            .map_err(|e| QueryError::Other { error: e.to_string() })?
+    );
+
+    for (i, attachment) in attachments.into_iter().enumerate() {
+        let part = attachment.into_part().await
+            .map_err(|e| QueryError::IoError { err: e })?;
+        form = form.part(format!("files[{i}]"), part);
+    }
+
+    Ok(form)
+}
+
+/// Builds a `multipart/form-data` body out of plain text fields and a single file.
+pub(crate) async fn multipart_fields_form(fields: Vec<(&'static str, String)>, file_field: &'static str, attachment: Attachment) -> Result<Form, QueryError> {
+    let mut form = fields.into_iter()
+        .fold(Form::new(), |form, (name, value)| form.text(name, value));
+
+    let part = attachment.into_part().await
+        .map_err(|e| QueryError::IoError { err: e })?;
+    form = form.part(file_field, part);
+
+    Ok(form)
+}
@@ -0,0 +1,41 @@
+use crate::model::guild::audit_log::EventType;
+use crate::model::Snowflake;
+
+/// Narrows down the entries of a guild's audit log.
+#[derive(Debug, Default, Clone)]
+pub struct AuditLogFilter {
+    pub(crate) user_id: Option<Snowflake>,
+    pub(crate) action_type: Option<EventType>,
+    pub(crate) before: Option<Snowflake>,
+    pub(crate) after: Option<Snowflake>,
+}
+
+impl AuditLogFilter {
+    pub fn new() -> AuditLogFilter {
+        AuditLogFilter::default()
+    }
+
+    /// Only actions made by this user.
+    pub fn user(mut self, user_id: &Snowflake) -> AuditLogFilter {
+        self.user_id = Some(user_id.clone());
+        self
+    }
+
+    pub fn action_type(mut self, action_type: EventType) -> AuditLogFilter {
+        self.action_type = Some(action_type);
+        self
+    }
+
+    /// Only entries older than this entry.
+    pub fn before(mut self, entry_id: &Snowflake) -> AuditLogFilter {
+        self.before = Some(entry_id.clone());
+        self
+    }
+
+    /// Only entries newer than this entry.
+    /// The entries are then listed oldest first.
+    pub fn after(mut self, entry_id: &Snowflake) -> AuditLogFilter {
+        self.after = Some(entry_id.clone());
+        self
+    }
+}
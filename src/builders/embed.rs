@@ -0,0 +1,126 @@
+use serde::Serialize;
+use time::OffsetDateTime;
+
+#[derive(Serialize, Debug, Clone)]
+struct EmbedFooter {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon_url: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct EmbedMedia {
+    url: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct EmbedAuthor {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon_url: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct EmbedField {
+    name: String,
+    value: String,
+    inline: bool,
+}
+
+/// A rich embed to send with a message.
+/// https://discord.com/developers/docs/resources/message#embed-object-embed-limits
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct CreateEmbed {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "time::serde::iso8601::option")]
+    timestamp: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    footer: Option<EmbedFooter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<EmbedMedia>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail: Option<EmbedMedia>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<EmbedAuthor>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<EmbedField>,
+}
+
+impl CreateEmbed {
+    pub fn new() -> CreateEmbed {
+        CreateEmbed::default()
+    }
+
+    pub fn title<S: AsRef<str>>(mut self, title: S) -> CreateEmbed {
+        self.title = Some(title.as_ref().to_string());
+        self
+    }
+
+    pub fn description<S: AsRef<str>>(mut self, description: S) -> CreateEmbed {
+        self.description = Some(description.as_ref().to_string());
+        self
+    }
+
+    /// Makes the title a link.
+    pub fn url<S: AsRef<str>>(mut self, url: S) -> CreateEmbed {
+        self.url = Some(url.as_ref().to_string());
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: OffsetDateTime) -> CreateEmbed {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// The color of the embed's side bar, as 0xRRGGBB.
+    pub fn color(mut self, color: u32) -> CreateEmbed {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn footer<S: AsRef<str>>(mut self, text: S, icon_url: Option<&str>) -> CreateEmbed {
+        self.footer = Some(EmbedFooter {
+            text: text.as_ref().to_string(),
+            icon_url: icon_url.map(str::to_string),
+        });
+        self
+    }
+
+    /// Use `attachment://filename.png` to show an image attached to the same message.
+    pub fn image<S: AsRef<str>>(mut self, url: S) -> CreateEmbed {
+        self.image = Some(EmbedMedia { url: url.as_ref().to_string() });
+        self
+    }
+
+    pub fn thumbnail<S: AsRef<str>>(mut self, url: S) -> CreateEmbed {
+        self.thumbnail = Some(EmbedMedia { url: url.as_ref().to_string() });
+        self
+    }
+
+    pub fn author<S: AsRef<str>>(mut self, name: S, url: Option<&str>, icon_url: Option<&str>) -> CreateEmbed {
+        self.author = Some(EmbedAuthor {
+            name: name.as_ref().to_string(),
+            url: url.map(str::to_string),
+            icon_url: icon_url.map(str::to_string),
+        });
+        self
+    }
+
+    pub fn field<N: AsRef<str>, V: AsRef<str>>(mut self, name: N, value: V, inline: bool) -> CreateEmbed {
+        self.fields.push(EmbedField {
+            name: name.as_ref().to_string(),
+            value: value.as_ref().to_string(),
+            inline,
+        });
+        self
+    }
+}
@@ -0,0 +1,56 @@
+use serde::Serialize;
+use crate::builders::image::ImageData;
+use crate::model::Snowflake;
+
+/// A custom emoji to upload to a guild.
+#[derive(Serialize, Debug, Clone)]
+pub struct CreateEmoji {
+    name: String,
+    image: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<Snowflake>,
+}
+
+impl CreateEmoji {
+    /// The image can be up to 256 KiB, a GIF makes an animated emoji.
+    pub fn new<S: AsRef<str>>(name: S, image: &ImageData) -> CreateEmoji {
+        CreateEmoji {
+            name: name.as_ref().to_string(),
+            image: image.as_data_uri().to_string(),
+            roles: Vec::new(),
+        }
+    }
+
+    /// Only members with one of these roles can use the emoji.
+    pub fn role(mut self, role_id: &Snowflake) -> CreateEmoji {
+        self.roles.push(role_id.clone());
+        self
+    }
+}
+
+/// Changes to make to a custom emoji.
+/// Fields that aren't set are left as they are.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct EditEmoji {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<Snowflake>>,
+}
+
+impl EditEmoji {
+    pub fn new() -> EditEmoji {
+        EditEmoji::default()
+    }
+
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> EditEmoji {
+        self.name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// Replaces the roles allowed to use the emoji, none lets everyone use it.
+    pub fn roles(mut self, roles: Vec<Snowflake>) -> EditEmoji {
+        self.roles = Some(roles);
+        self
+    }
+}
@@ -0,0 +1,118 @@
+use serde::Serialize;
+use crate::model::channel::{AutoArchiveDuration, GuildForumTag};
+use crate::model::Snowflake;
+use super::attachment::Attachment;
+use super::message::CreateMessage;
+
+/// A post to create in a forum or media channel, a thread along with its starter message.
+#[derive(Serialize, Debug)]
+pub struct CreateForumPost {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_archive_duration: Option<AutoArchiveDuration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    applied_tags: Vec<Snowflake>,
+    message: CreateMessage,
+}
+
+impl CreateForumPost {
+    /// Media channel posts need at least one attachment in the message.
+    pub fn new<S: AsRef<str>, M: Into<CreateMessage>>(name: S, message: M) -> CreateForumPost {
+        CreateForumPost {
+            name: name.as_ref().to_string(),
+            auto_archive_duration: None,
+            rate_limit_per_user: None,
+            applied_tags: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn auto_archive_duration(mut self, duration: AutoArchiveDuration) -> CreateForumPost {
+        self.auto_archive_duration = Some(duration);
+        self
+    }
+
+    /// Slowmode, in seconds. Up to 21600.
+    pub fn rate_limit_per_user(mut self, seconds: u32) -> CreateForumPost {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// Up to 5 tags, from the channel's `available_tags`.
+    /// Required if the channel has the `REQUIRE_TAG` flag.
+    pub fn tag(mut self, tag_id: &Snowflake) -> CreateForumPost {
+        self.applied_tags.push(tag_id.clone());
+        self
+    }
+
+    /// Splits off the starter message's files, describing them in its attachments.
+    pub(crate) fn into_payload(mut self) -> (CreateForumPost, Vec<Attachment>) {
+        let (message, files) = self.message.into_payload();
+        self.message = message;
+        (self, files)
+    }
+}
+
+/// A tag in the set of tags a forum or media channel offers.
+#[derive(Serialize, Debug, Clone)]
+pub struct CreateForumTag {
+    /// Set for tags that already exist, to keep them as they are.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Snowflake>,
+    name: String,
+    moderated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji_id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji_name: Option<String>,
+}
+
+impl CreateForumTag {
+    /// The name can be up to 20 characters.
+    pub fn new<S: AsRef<str>>(name: S) -> CreateForumTag {
+        CreateForumTag {
+            id: None,
+            name: name.as_ref().to_string(),
+            moderated: false,
+            emoji_id: None,
+            emoji_name: None,
+        }
+    }
+
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> CreateForumTag {
+        self.name = name.as_ref().to_string();
+        self
+    }
+
+    /// Only users with `MANAGE_THREADS` can apply moderated tags.
+    pub fn moderated(mut self, moderated: bool) -> CreateForumTag {
+        self.moderated = moderated;
+        self
+    }
+
+    pub fn custom_emoji(mut self, emoji_id: &Snowflake) -> CreateForumTag {
+        self.emoji_id = Some(emoji_id.clone());
+        self.emoji_name = None;
+        self
+    }
+
+    pub fn unicode_emoji<S: AsRef<str>>(mut self, emoji: S) -> CreateForumTag {
+        self.emoji_name = Some(emoji.as_ref().to_string());
+        self.emoji_id = None;
+        self
+    }
+}
+
+impl From<&GuildForumTag> for CreateForumTag {
+    fn from(tag: &GuildForumTag) -> CreateForumTag {
+        CreateForumTag {
+            id: Some(tag.id.clone()),
+            name: tag.name.clone(),
+            moderated: tag.moderated,
+            emoji_id: tag.emoji_id.clone(),
+            emoji_name: tag.emoji_name.clone(),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use serde::Serialize;
+use crate::model::guild::{DefaultMessageNotificationLevel, ExplicitContentFilterLevel, VerificationLevel};
+use crate::model::Snowflake;
+
+/// Changes to make to a guild's settings.
+/// Fields that aren't set are left as they are.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct ModifyGuild {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification_level: Option<VerificationLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_message_notifications: Option<DefaultMessageNotificationLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explicit_content_filter: Option<ExplicitContentFilterLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    afk_channel_id: Option<Option<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    afk_timeout: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_channel_id: Option<Option<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_channel_flags: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rules_channel_id: Option<Option<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_updates_channel_id: Option<Option<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preferred_locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    features: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    premium_progress_bar_enabled: Option<bool>,
+}
+
+impl ModifyGuild {
+    pub fn new() -> ModifyGuild {
+        ModifyGuild::default()
+    }
+
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> ModifyGuild {
+        self.name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// None removes the description.
+    pub fn description(mut self, description: Option<&str>) -> ModifyGuild {
+        self.description = Some(description.map(str::to_string));
+        self
+    }
+
+    pub fn verification_level(mut self, level: VerificationLevel) -> ModifyGuild {
+        self.verification_level = Some(level);
+        self
+    }
+
+    pub fn default_message_notifications(mut self, level: DefaultMessageNotificationLevel) -> ModifyGuild {
+        self.default_message_notifications = Some(level);
+        self
+    }
+
+    pub fn explicit_content_filter(mut self, level: ExplicitContentFilterLevel) -> ModifyGuild {
+        self.explicit_content_filter = Some(level);
+        self
+    }
+
+    /// None removes the afk channel.
+    pub fn afk_channel(mut self, channel_id: Option<&Snowflake>) -> ModifyGuild {
+        self.afk_channel_id = Some(channel_id.cloned());
+        self
+    }
+
+    /// In seconds, one of 60, 300, 900, 1800 or 3600.
+    pub fn afk_timeout(mut self, seconds: u32) -> ModifyGuild {
+        self.afk_timeout = Some(seconds);
+        self
+    }
+
+    /// None stops join and boost messages from being sent.
+    pub fn system_channel(mut self, channel_id: Option<&Snowflake>) -> ModifyGuild {
+        self.system_channel_id = Some(channel_id.cloned());
+        self
+    }
+
+    /// See `system_channel_flag`.
+    pub fn system_channel_flags(mut self, flags: u64) -> ModifyGuild {
+        self.system_channel_flags = Some(flags);
+        self
+    }
+
+    /// Only for community guilds.
+    pub fn rules_channel(mut self, channel_id: Option<&Snowflake>) -> ModifyGuild {
+        self.rules_channel_id = Some(channel_id.cloned());
+        self
+    }
+
+    /// Only for community guilds.
+    pub fn public_updates_channel(mut self, channel_id: Option<&Snowflake>) -> ModifyGuild {
+        self.public_updates_channel_id = Some(channel_id.cloned());
+        self
+    }
+
+    /// e.g. "en-US".
+    pub fn preferred_locale<S: AsRef<str>>(mut self, locale: S) -> ModifyGuild {
+        self.preferred_locale = Some(locale.as_ref().to_string());
+        self
+    }
+
+    /// Replaces the guild's features, only some of them can be changed.
+    pub fn features(mut self, features: Vec<String>) -> ModifyGuild {
+        self.features = Some(features);
+        self
+    }
+
+    pub fn premium_progress_bar_enabled(mut self, enabled: bool) -> ModifyGuild {
+        self.premium_progress_bar_enabled = Some(enabled);
+        self
+    }
+}
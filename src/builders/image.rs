@@ -0,0 +1,45 @@
+use std::path::Path;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use crate::http::QueryError;
+
+/// An image sent inline as a data URI, for emojis, icons and avatars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageData {
+    uri: String,
+}
+
+impl ImageData {
+    /// The format is detected from the image itself, PNG, JPEG, GIF, WebP and AVIF are supported.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ImageData, QueryError> {
+        let mime = image_mime(bytes).ok_or_else(|| QueryError::InvalidRequest {
+            error: "Unsupported image format, expected PNG, JPEG, GIF, WebP or AVIF".to_string()
+        })?;
+
+        Ok(ImageData {
+            uri: format!("data:{mime};base64,{}", STANDARD.encode(bytes)),
+        })
+    }
+
+    pub async fn from_path<P: AsRef<Path>>(path: P) -> Result<ImageData, QueryError> {
+        let bytes = tokio::fs::read(path).await
+            .map_err(|e| QueryError::IoError { err: e })?;
+        ImageData::from_bytes(&bytes)
+    }
+
+    /// E.g. `data:image/png;base64,...`
+    pub fn as_data_uri(&self) -> &str {
+        &self.uri
+    }
+}
+
+fn image_mime(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        [_, _, _, _, b'f', b't', b'y', b'p', b'a', b'v', b'i', b'f', ..] => Some("image/avif"),
+        _ => None,
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Serialize;
+use crate::model::invite::InviteTargetType;
+use crate::model::Snowflake;
+
+/// An invite to create for a channel.
+/// By default it lasts 24 hours and has unlimited uses.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct CreateInvite {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_age: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_uses: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temporary: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unique: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_type: Option<InviteTargetType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_user_id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_application_id: Option<Snowflake>,
+}
+
+impl CreateInvite {
+    pub fn new() -> CreateInvite {
+        CreateInvite::default()
+    }
+
+    /// In seconds, up to 604800 (7 days). 0 for an invite that never expires.
+    pub fn max_age(mut self, seconds: u32) -> CreateInvite {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// Up to 100, 0 for unlimited uses.
+    pub fn max_uses(mut self, max_uses: u32) -> CreateInvite {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Members that joined with the invite are kicked once they disconnect, unless they were given a role.
+    pub fn temporary(mut self, temporary: bool) -> CreateInvite {
+        self.temporary = Some(temporary);
+        self
+    }
+
+    /// Always creates a new invite, instead of reusing a similar one.
+    pub fn unique(mut self, unique: bool) -> CreateInvite {
+        self.unique = Some(unique);
+        self
+    }
+
+    /// Opens a user's stream in the voice channel once joined.
+    pub fn target_stream(mut self, user_id: &Snowflake) -> CreateInvite {
+        self.target_type = Some(InviteTargetType::Stream);
+        self.target_user_id = Some(user_id.clone());
+        self.target_application_id = None;
+        self
+    }
+
+    /// Opens an embedded application in the voice channel once joined.
+    pub fn target_application(mut self, application_id: &Snowflake) -> CreateInvite {
+        self.target_type = Some(InviteTargetType::EmbeddedApplication);
+        self.target_application_id = Some(application_id.clone());
+        self.target_user_id = None;
+        self
+    }
+}
@@ -0,0 +1,74 @@
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use crate::model::Snowflake;
+
+/// Changes to make to a guild member.
+/// Fields that aren't set are left as they are.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct ModifyMember {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nick: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deaf: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Option<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    communication_disabled_until: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u64>,
+}
+
+impl ModifyMember {
+    pub fn new() -> ModifyMember {
+        ModifyMember::default()
+    }
+
+    /// None resets the nickname. Needs the `MANAGE_NICKNAMES` permission.
+    pub fn nick(mut self, nick: Option<&str>) -> ModifyMember {
+        self.nick = Some(nick.map(str::to_string));
+        self
+    }
+
+    /// Replaces the member's roles. Needs the `MANAGE_ROLES` permission.
+    pub fn roles(mut self, role_ids: &[Snowflake]) -> ModifyMember {
+        self.roles = Some(role_ids.to_vec());
+        self
+    }
+
+    /// Only works while the member is in a voice channel. Needs the `MUTE_MEMBERS` permission.
+    pub fn mute(mut self, mute: bool) -> ModifyMember {
+        self.mute = Some(mute);
+        self
+    }
+
+    /// Only works while the member is in a voice channel. Needs the `DEAFEN_MEMBERS` permission.
+    pub fn deaf(mut self, deaf: bool) -> ModifyMember {
+        self.deaf = Some(deaf);
+        self
+    }
+
+    /// Moves the member to another voice channel, or disconnects them with None.
+    /// Needs the `MOVE_MEMBERS` permission.
+    pub fn voice_channel(mut self, channel_id: Option<&Snowflake>) -> ModifyMember {
+        self.channel_id = Some(channel_id.cloned());
+        self
+    }
+
+    /// Times the member out until then, up to 28 days from now. None removes the timeout.
+    /// Needs the `MODERATE_MEMBERS` permission.
+    pub fn timeout_until(mut self, until: Option<OffsetDateTime>) -> ModifyMember {
+        self.communication_disabled_until = Some(until.and_then(|until| until.format(&Rfc3339).ok()));
+        self
+    }
+
+    /// See `guild_member_flag`, only `BYPASSES_VERIFICATION` can be changed.
+    pub fn flags(mut self, flags: u64) -> ModifyMember {
+        self.flags = Some(flags);
+        self
+    }
+}
@@ -0,0 +1,484 @@
+use serde::Serialize;
+use serde_repr::Serialize_repr;
+use serde_json::{json, Value};
+use crate::model::message::message_flag;
+use crate::model::Snowflake;
+use super::attachment::{Attachment, AttachmentPayload};
+use super::embed::CreateEmbed;
+
+#[derive(Serialize_repr, Debug, Clone, Copy)]
+#[repr(u8)]
+enum MessageReferenceType {
+    Default = 0,
+    Forward = 1,
+}
+
+/// Points a message at another message, either to reply to or forward it.
+#[derive(Serialize, Debug, Clone)]
+pub struct MessageReference {
+    #[serde(rename = "type")]
+    r#type: MessageReferenceType,
+    message_id: Snowflake,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    guild_id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fail_if_not_exists: Option<bool>,
+}
+
+impl MessageReference {
+    /// Replies to a message in the same channel.
+    pub fn reply(message_id: &Snowflake) -> MessageReference {
+        MessageReference {
+            r#type: MessageReferenceType::Default,
+            message_id: message_id.clone(),
+            channel_id: None,
+            guild_id: None,
+            fail_if_not_exists: None,
+        }
+    }
+
+    /// Forwards a message from any channel the user can see.
+    pub fn forward(channel_id: &Snowflake, message_id: &Snowflake) -> MessageReference {
+        MessageReference {
+            r#type: MessageReferenceType::Forward,
+            message_id: message_id.clone(),
+            channel_id: Some(channel_id.clone()),
+            guild_id: None,
+            fail_if_not_exists: None,
+        }
+    }
+
+    pub fn guild_id(mut self, guild_id: &Snowflake) -> MessageReference {
+        self.guild_id = Some(guild_id.clone());
+        self
+    }
+
+    /// If false, the message is still sent when the referenced message doesn't exist.
+    /// Defaults to true.
+    pub fn fail_if_not_exists(mut self, fail_if_not_exists: bool) -> MessageReference {
+        self.fail_if_not_exists = Some(fail_if_not_exists);
+        self
+    }
+}
+
+/// Controls who gets pinged by the mentions in a message.
+/// https://discord.com/developers/docs/resources/message#allowed-mentions-object
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AllowedMentions {
+    parse: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<Snowflake>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    users: Vec<Snowflake>,
+    replied_user: bool,
+}
+
+impl AllowedMentions {
+    /// Nobody is pinged.
+    pub fn none() -> AllowedMentions {
+        AllowedMentions::default()
+    }
+
+    /// Every mention in the content pings, including the replied to user.
+    pub fn all() -> AllowedMentions {
+        AllowedMentions {
+            parse: vec!["users", "roles", "everyone"],
+            replied_user: true,
+            ..Default::default()
+        }
+    }
+
+    /// Pings every user mentioned in the content.
+    pub fn all_users(mut self) -> AllowedMentions {
+        self.users.clear();
+        self.add_parse("users");
+        self
+    }
+
+    /// Pings every role mentioned in the content.
+    pub fn all_roles(mut self) -> AllowedMentions {
+        self.roles.clear();
+        self.add_parse("roles");
+        self
+    }
+
+    /// Allows @everyone and @here to ping.
+    pub fn everyone(mut self) -> AllowedMentions {
+        self.add_parse("everyone");
+        self
+    }
+
+    /// Only these users are pinged. Up to 100.
+    pub fn users(mut self, user_ids: &[Snowflake]) -> AllowedMentions {
+        self.parse.retain(|p| *p != "users");
+        self.users = user_ids.to_vec();
+        self
+    }
+
+    /// Only these roles are pinged. Up to 100.
+    pub fn roles(mut self, role_ids: &[Snowflake]) -> AllowedMentions {
+        self.parse.retain(|p| *p != "roles");
+        self.roles = role_ids.to_vec();
+        self
+    }
+
+    /// Whether the author of the replied to message is pinged.
+    pub fn replied_user(mut self, replied_user: bool) -> AllowedMentions {
+        self.replied_user = replied_user;
+        self
+    }
+
+    fn add_parse(&mut self, parse: &'static str) {
+        if !self.parse.contains(&parse) {
+            self.parse.push(parse);
+        }
+    }
+}
+
+/// A message to send.
+/// Plain strings convert into a message with just that content.
+#[derive(Serialize, Debug, Default)]
+pub struct CreateMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enforce_nonce: Option<bool>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    tts: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<CreateEmbed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_reference: Option<MessageReference>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    components: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sticker_ids: Vec<Snowflake>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u64>,
+    #[serde(skip)]
+    files: Vec<Attachment>,
+}
+
+impl CreateMessage {
+    pub fn new() -> CreateMessage {
+        CreateMessage::default()
+    }
+
+    pub fn content<S: AsRef<str>>(mut self, content: S) -> CreateMessage {
+        self.content = Some(content.as_ref().to_string());
+        self
+    }
+
+    /// Used to confirm the message was sent, it's echoed back in the created message.
+    /// With `enforce_nonce`, sending another message with the same nonce within a few minutes
+    /// returns the first message instead of creating a duplicate.
+    pub fn nonce<S: AsRef<str>>(mut self, nonce: S, enforce_nonce: bool) -> CreateMessage {
+        self.nonce = Some(nonce.as_ref().to_string());
+        self.enforce_nonce = Some(enforce_nonce);
+        self
+    }
+
+    pub fn tts(mut self, tts: bool) -> CreateMessage {
+        self.tts = tts;
+        self
+    }
+
+    /// Up to 10 embeds can be sent in a message.
+    pub fn embed(mut self, embed: CreateEmbed) -> CreateMessage {
+        self.embeds.push(embed);
+        self
+    }
+
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> CreateMessage {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Replies to a message in the same channel.
+    pub fn reply_to(self, message_id: &Snowflake) -> CreateMessage {
+        self.reference(MessageReference::reply(message_id))
+    }
+
+    /// Replies to or forwards a message.
+    pub fn reference(mut self, reference: MessageReference) -> CreateMessage {
+        self.message_reference = Some(reference);
+        self
+    }
+
+    /// Raw message components (buttons, select menus...), as described in
+    /// https://discord.com/developers/docs/interactions/message-components
+    pub fn component(mut self, component: Value) -> CreateMessage {
+        self.components.push(component);
+        self
+    }
+
+    /// Up to 3 stickers can be sent in a message.
+    pub fn sticker(mut self, sticker_id: &Snowflake) -> CreateMessage {
+        self.sticker_ids.push(sticker_id.clone());
+        self
+    }
+
+    /// Sends the message as `multipart/form-data` with the file attached.
+    pub fn attachment(mut self, attachment: Attachment) -> CreateMessage {
+        self.files.push(attachment);
+        self
+    }
+
+    pub fn attachments<I: IntoIterator<Item = Attachment>>(mut self, attachments: I) -> CreateMessage {
+        self.files.extend(attachments);
+        self
+    }
+
+    /// Hides the embeds generated for links in the content.
+    pub fn suppress_embeds(self, suppress_embeds: bool) -> CreateMessage {
+        self.set_flag(message_flag::SUPPRESS_EMBEDS, suppress_embeds)
+    }
+
+    /// Sends the message without pushing notifications to the users it mentions.
+    pub fn silent(self, silent: bool) -> CreateMessage {
+        self.set_flag(message_flag::SUPPRESS_NOTIFICATIONS, silent)
+    }
+
+    fn set_flag(mut self, flag: u64, set: bool) -> CreateMessage {
+        let flags = self.flags.unwrap_or_default();
+        self.flags = Some(if set { flags | flag } else { flags & !flag });
+        self
+    }
+
+    pub(crate) fn content_str(&self) -> &str {
+        self.content.as_deref().unwrap_or_default()
+    }
+
+    /// Splits off the files, describing them in the payload's attachments.
+    pub(crate) fn into_payload(mut self) -> (CreateMessage, Vec<Attachment>) {
+        let files = std::mem::take(&mut self.files);
+        self.attachments = files.iter()
+            .enumerate()
+            .map(|(i, attachment)| attachment.payload(i))
+            .collect();
+        (self, files)
+    }
+}
+
+impl From<&str> for CreateMessage {
+    fn from(content: &str) -> CreateMessage {
+        CreateMessage::new().content(content)
+    }
+}
+
+impl From<String> for CreateMessage {
+    fn from(content: String) -> CreateMessage {
+        CreateMessage {
+            content: Some(content),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&String> for CreateMessage {
+    fn from(content: &String) -> CreateMessage {
+        CreateMessage::new().content(content)
+    }
+}
+
+/// Changes to make to an existing message.
+/// Fields that aren't set are left as they are.
+#[derive(Serialize, Debug, Default)]
+pub struct EditMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embeds: Option<Vec<CreateEmbed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<Vec<Value>>,
+    #[serde(skip)]
+    files: Vec<Attachment>,
+}
+
+impl EditMessage {
+    pub fn new() -> EditMessage {
+        EditMessage::default()
+    }
+
+    /// An empty string removes the content.
+    pub fn content<S: AsRef<str>>(mut self, content: S) -> EditMessage {
+        self.content = Some(content.as_ref().to_string());
+        self
+    }
+
+    /// Replaces the embeds of the message. Up to 10.
+    pub fn embeds(mut self, embeds: Vec<CreateEmbed>) -> EditMessage {
+        self.embeds = Some(embeds);
+        self
+    }
+
+    /// Adds an embed to the ones replacing the embeds of the message.
+    pub fn embed(mut self, embed: CreateEmbed) -> EditMessage {
+        self.embeds.get_or_insert_with(Vec::new).push(embed);
+        self
+    }
+
+    /// Hides the embeds generated for links in the content.
+    /// Only this flag can be changed after the message is sent.
+    pub fn suppress_embeds(mut self, suppress_embeds: bool) -> EditMessage {
+        self.flags = Some(if suppress_embeds { message_flag::SUPPRESS_EMBEDS } else { 0 });
+        self
+    }
+
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> EditMessage {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Replaces the components of the message with these raw components.
+    pub fn components(mut self, components: Vec<Value>) -> EditMessage {
+        self.components = Some(components);
+        self
+    }
+
+    /// Keeps an attachment the message already has.
+    /// Once the attachments are changed, the ones that aren't kept are removed.
+    pub fn keep_attachment(mut self, attachment_id: &Snowflake) -> EditMessage {
+        self.attachments.get_or_insert_with(Vec::new).push(json!({ "id": attachment_id }));
+        self
+    }
+
+    /// Removes every attachment the message has, apart from those kept with `keep_attachment`.
+    pub fn remove_attachments(mut self) -> EditMessage {
+        self.attachments.get_or_insert_with(Vec::new);
+        self
+    }
+
+    /// Uploads a new file to the message.
+    /// Existing attachments are removed unless kept with `keep_attachment`.
+    pub fn attachment(mut self, attachment: Attachment) -> EditMessage {
+        self.files.push(attachment);
+        self
+    }
+
+    /// Splits off the new files, describing them in the payload's attachments.
+    pub(crate) fn into_payload(mut self) -> (EditMessage, Vec<Attachment>) {
+        let files = std::mem::take(&mut self.files);
+        if !files.is_empty() {
+            let attachments = self.attachments.get_or_insert_with(Vec::new);
+            for (i, attachment) in files.iter().enumerate() {
+                attachments.push(json!(attachment.payload(i)));
+            }
+        }
+        (self, files)
+    }
+}
+
+impl From<&str> for EditMessage {
+    fn from(content: &str) -> EditMessage {
+        EditMessage::new().content(content)
+    }
+}
+
+impl From<String> for EditMessage {
+    fn from(content: String) -> EditMessage {
+        EditMessage {
+            content: Some(content),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_json<T: Serialize>(value: &T) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn unset_fields_are_omitted() {
+        assert_eq!(to_json(&CreateMessage::new()), json!({}));
+        assert_eq!(to_json(&CreateMessage::from("hi")), json!({ "content": "hi" }));
+        assert_eq!(to_json(&CreateMessage::new().tts(false)), json!({}));
+        assert_eq!(to_json(&EditMessage::new()), json!({}));
+    }
+
+    #[test]
+    fn replies_reference_the_message() {
+        let message = CreateMessage::from("hi").reply_to(&Snowflake::new("123"));
+        assert_eq!(to_json(&message), json!({
+            "content": "hi",
+            "message_reference": { "type": 0, "message_id": "123" },
+        }));
+
+        let forward = MessageReference::forward(&Snowflake::new("1"), &Snowflake::new("2"))
+            .guild_id(&Snowflake::new("3"))
+            .fail_if_not_exists(false);
+        assert_eq!(to_json(&forward), json!({
+            "type": 1,
+            "message_id": "2",
+            "channel_id": "1",
+            "guild_id": "3",
+            "fail_if_not_exists": false,
+        }));
+    }
+
+    #[test]
+    fn allowed_mentions_always_send_parse_and_replied_user() {
+        assert_eq!(to_json(&AllowedMentions::none()), json!({ "parse": [], "replied_user": false }));
+        assert_eq!(to_json(&AllowedMentions::all()), json!({
+            "parse": ["users", "roles", "everyone"],
+            "replied_user": true,
+        }));
+
+        // Listing users replaces parsing them, discord refuses both at once.
+        let mentions = AllowedMentions::all()
+            .users(&[Snowflake::new("1"), Snowflake::new("2")])
+            .replied_user(false);
+        assert_eq!(to_json(&mentions), json!({
+            "parse": ["roles", "everyone"],
+            "users": ["1", "2"],
+            "replied_user": false,
+        }));
+    }
+
+    #[test]
+    fn flags_are_sent_as_an_integer() {
+        let message = CreateMessage::new().suppress_embeds(true).silent(true);
+        assert_eq!(to_json(&message), json!({ "flags": 4 | 4096 }));
+
+        let message = CreateMessage::new().silent(true).silent(false);
+        assert_eq!(to_json(&message), json!({ "flags": 0 }));
+
+        let edit = EditMessage::new().suppress_embeds(true);
+        assert_eq!(to_json(&edit), json!({ "flags": 4 }));
+    }
+
+    #[test]
+    fn files_are_described_in_the_payload() {
+        let message = CreateMessage::from("hi")
+            .attachment(Attachment::from_bytes("a.png", vec![1]).description("first"))
+            .attachment(Attachment::from_bytes("b.png", vec![2]).spoiler(true));
+        let (payload, files) = message.into_payload();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(to_json(&payload), json!({
+            "content": "hi",
+            "attachments": [
+                { "id": 0, "filename": "a.png", "description": "first" },
+                { "id": 1, "filename": "SPOILER_b.png" },
+            ],
+        }));
+    }
+}
@@ -0,0 +1,15 @@
+pub mod attachment;
+pub mod audit_log;
+pub mod embed;
+pub mod emoji;
+pub mod forum;
+pub mod guild;
+pub mod image;
+pub mod invite;
+pub mod member;
+pub mod message;
+pub mod role;
+pub mod scheduled_event;
+pub mod sticker;
+pub mod thread;
+pub mod webhook;
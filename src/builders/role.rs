@@ -0,0 +1,89 @@
+use serde::Serialize;
+use crate::model::guild::RoleColors;
+use crate::model::permissions::Permissions;
+use crate::model::Snowflake;
+
+/// A role to create, or changes to make to an existing one.
+/// Fields that aren't set are left as they are, or to discord's defaults for new roles.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct EditRole {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    colors: Option<RoleColors>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_emoji: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionable: Option<bool>,
+}
+
+impl EditRole {
+    pub fn new() -> EditRole {
+        EditRole::default()
+    }
+
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> EditRole {
+        self.name = Some(name.as_ref().to_string());
+        self
+    }
+
+    pub fn permissions(mut self, permissions: Permissions) -> EditRole {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// 0xRRGGBB, 0 for no color.
+    pub fn color(mut self, color: u32) -> EditRole {
+        self.color = Some(color);
+        self
+    }
+
+    /// A gradient between two colors, needs the guild to have the `ENHANCED_ROLE_COLORS` feature.
+    pub fn gradient(mut self, primary_color: u32, secondary_color: u32) -> EditRole {
+        self.colors = Some(RoleColors {
+            primary_color,
+            secondary_color: Some(secondary_color),
+            tertiary_color: None,
+        });
+        self
+    }
+
+    /// Shows the role's members separately in the member list.
+    pub fn hoist(mut self, hoist: bool) -> EditRole {
+        self.hoist = Some(hoist);
+        self
+    }
+
+    /// The icon as a data URI (e.g. `data:image/png;base64,...`), None removes it.
+    /// Needs the guild to have the `ROLE_ICONS` feature.
+    pub fn icon(mut self, icon: Option<&str>) -> EditRole {
+        self.icon = Some(icon.map(str::to_string));
+        self
+    }
+
+    /// A unicode emoji shown as the role's icon, None removes it.
+    pub fn unicode_emoji(mut self, emoji: Option<&str>) -> EditRole {
+        self.unicode_emoji = Some(emoji.map(str::to_string));
+        self
+    }
+
+    pub fn mentionable(mut self, mentionable: bool) -> EditRole {
+        self.mentionable = Some(mentionable);
+        self
+    }
+}
+
+/// A role's new position, see `DiscordClient::reorder_roles`.
+#[derive(Serialize, Debug, Clone)]
+pub struct RolePosition {
+    pub id: Snowflake,
+    pub position: i32,
+}
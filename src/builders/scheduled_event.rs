@@ -0,0 +1,190 @@
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use crate::builders::image::ImageData;
+use crate::model::scheduled_event::{RecurrenceRule, ScheduledEventEntityType, ScheduledEventMetadata, ScheduledEventPrivacyLevel, ScheduledEventStatus};
+use crate::model::Snowflake;
+
+fn format_time(time: OffsetDateTime) -> Option<String> {
+    time.format(&Rfc3339).ok()
+}
+
+/// A scheduled event to create in a guild.
+#[derive(Serialize, Debug, Clone)]
+pub struct CreateScheduledEvent {
+    name: String,
+    privacy_level: ScheduledEventPrivacyLevel,
+    scheduled_start_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_end_time: Option<String>,
+    entity_type: ScheduledEventEntityType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_metadata: Option<ScheduledEventMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recurrence_rule: Option<RecurrenceRule>,
+}
+
+impl CreateScheduledEvent {
+    fn new<S: AsRef<str>>(name: S, start: OffsetDateTime, entity_type: ScheduledEventEntityType) -> CreateScheduledEvent {
+        CreateScheduledEvent {
+            name: name.as_ref().to_string(),
+            privacy_level: ScheduledEventPrivacyLevel::GuildOnly,
+            scheduled_start_time: format_time(start),
+            scheduled_end_time: None,
+            entity_type,
+            channel_id: None,
+            entity_metadata: None,
+            description: None,
+            image: None,
+            recurrence_rule: None,
+        }
+    }
+
+    /// An event in a voice channel.
+    pub fn voice<S: AsRef<str>>(name: S, channel_id: &Snowflake, start: OffsetDateTime) -> CreateScheduledEvent {
+        let mut event = CreateScheduledEvent::new(name, start, ScheduledEventEntityType::Voice);
+        event.channel_id = Some(channel_id.clone());
+        event
+    }
+
+    /// An event in a stage channel.
+    pub fn stage<S: AsRef<str>>(name: S, channel_id: &Snowflake, start: OffsetDateTime) -> CreateScheduledEvent {
+        let mut event = CreateScheduledEvent::new(name, start, ScheduledEventEntityType::StageInstance);
+        event.channel_id = Some(channel_id.clone());
+        event
+    }
+
+    /// An event outside of discord, these need an end time.
+    pub fn external<S: AsRef<str>, L: AsRef<str>>(
+        name: S,
+        location: L,
+        start: OffsetDateTime,
+        end: OffsetDateTime
+    ) -> CreateScheduledEvent {
+        let mut event = CreateScheduledEvent::new(name, start, ScheduledEventEntityType::External);
+        event.entity_metadata = Some(ScheduledEventMetadata { location: Some(location.as_ref().to_string()) });
+        event.scheduled_end_time = format_time(end);
+        event
+    }
+
+    pub fn end_time(mut self, end: OffsetDateTime) -> CreateScheduledEvent {
+        self.scheduled_end_time = format_time(end);
+        self
+    }
+
+    pub fn description<S: AsRef<str>>(mut self, description: S) -> CreateScheduledEvent {
+        self.description = Some(description.as_ref().to_string());
+        self
+    }
+
+    /// The event's cover image.
+    pub fn image(mut self, image: &ImageData) -> CreateScheduledEvent {
+        self.image = Some(image.as_data_uri().to_string());
+        self
+    }
+
+    pub fn recurrence(mut self, rule: RecurrenceRule) -> CreateScheduledEvent {
+        self.recurrence_rule = Some(rule);
+        self
+    }
+}
+
+/// Changes to make to a scheduled event.
+/// Fields that aren't set are left as they are.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct ModifyScheduledEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_start_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_end_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_type: Option<ScheduledEventEntityType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Option<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_metadata: Option<ScheduledEventMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<ScheduledEventStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recurrence_rule: Option<Option<RecurrenceRule>>,
+}
+
+impl ModifyScheduledEvent {
+    pub fn new() -> ModifyScheduledEvent {
+        ModifyScheduledEvent::default()
+    }
+
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> ModifyScheduledEvent {
+        self.name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// None removes the description.
+    pub fn description(mut self, description: Option<&str>) -> ModifyScheduledEvent {
+        self.description = Some(description.map(str::to_string));
+        self
+    }
+
+    pub fn start_time(mut self, start: OffsetDateTime) -> ModifyScheduledEvent {
+        self.scheduled_start_time = format_time(start);
+        self
+    }
+
+    pub fn end_time(mut self, end: OffsetDateTime) -> ModifyScheduledEvent {
+        self.scheduled_end_time = format_time(end);
+        self
+    }
+
+    /// Moves the event to a voice channel.
+    pub fn voice(mut self, channel_id: &Snowflake) -> ModifyScheduledEvent {
+        self.entity_type = Some(ScheduledEventEntityType::Voice);
+        self.channel_id = Some(Some(channel_id.clone()));
+        self
+    }
+
+    /// Moves the event to a stage channel.
+    pub fn stage(mut self, channel_id: &Snowflake) -> ModifyScheduledEvent {
+        self.entity_type = Some(ScheduledEventEntityType::StageInstance);
+        self.channel_id = Some(Some(channel_id.clone()));
+        self
+    }
+
+    /// Moves the event outside of discord, it needs an end time too unless it already has one.
+    pub fn external<S: AsRef<str>>(mut self, location: S) -> ModifyScheduledEvent {
+        self.entity_type = Some(ScheduledEventEntityType::External);
+        self.channel_id = Some(None);
+        self.entity_metadata = Some(ScheduledEventMetadata { location: Some(location.as_ref().to_string()) });
+        self
+    }
+
+    /// Starts, ends or cancels the event.
+    /// Scheduled events can become active or canceled, and active events can become completed.
+    pub fn status(mut self, status: ScheduledEventStatus) -> ModifyScheduledEvent {
+        self.status = Some(status);
+        self
+    }
+
+    /// The event's cover image.
+    pub fn image(mut self, image: &ImageData) -> ModifyScheduledEvent {
+        self.image = Some(image.as_data_uri().to_string());
+        self
+    }
+
+    /// None stops the event from repeating.
+    pub fn recurrence(mut self, rule: Option<RecurrenceRule>) -> ModifyScheduledEvent {
+        self.recurrence_rule = Some(rule);
+        self
+    }
+}
@@ -0,0 +1,72 @@
+use serde::Serialize;
+use crate::builders::attachment::Attachment;
+
+/// A sticker to upload to a guild.
+#[derive(Debug)]
+pub struct CreateSticker {
+    name: String,
+    description: String,
+    tags: String,
+    file: Attachment,
+}
+
+impl CreateSticker {
+    /// The file is a PNG, APNG, GIF or Lottie JSON file of up to 512 KiB.
+    /// `tags` are the autocomplete keywords, comma separated.
+    pub fn new<S: AsRef<str>, T: AsRef<str>>(name: S, tags: T, file: Attachment) -> CreateSticker {
+        CreateSticker {
+            name: name.as_ref().to_string(),
+            description: String::new(),
+            tags: tags.as_ref().to_string(),
+            file,
+        }
+    }
+
+    pub fn description<S: AsRef<str>>(mut self, description: S) -> CreateSticker {
+        self.description = description.as_ref().to_string();
+        self
+    }
+
+    /// The form fields to send along with the file.
+    pub(crate) fn into_fields(self) -> (Vec<(&'static str, String)>, Attachment) {
+        (
+            vec![("name", self.name), ("description", self.description), ("tags", self.tags)],
+            self.file
+        )
+    }
+}
+
+/// Changes to make to a guild sticker.
+/// Fields that aren't set are left as they are.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct EditSticker {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<String>,
+}
+
+impl EditSticker {
+    pub fn new() -> EditSticker {
+        EditSticker::default()
+    }
+
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> EditSticker {
+        self.name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// None removes the description.
+    pub fn description(mut self, description: Option<&str>) -> EditSticker {
+        self.description = Some(description.map(str::to_string));
+        self
+    }
+
+    /// The autocomplete keywords, comma separated.
+    pub fn tags<S: AsRef<str>>(mut self, tags: S) -> EditSticker {
+        self.tags = Some(tags.as_ref().to_string());
+        self
+    }
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+use serde_repr::Serialize_repr;
+use crate::model::channel::AutoArchiveDuration;
+
+/// The kind of thread to start when it isn't started from a message.
+#[derive(Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ThreadType {
+    /// Only for announcement channels.
+    Announcement = 10,
+    Public = 11,
+    /// Only visible to those invited and moderators.
+    Private = 12,
+}
+
+/// A thread to start in a channel, either from a message or on its own.
+#[derive(Serialize, Debug, Clone)]
+pub struct CreateThread {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_archive_duration: Option<AutoArchiveDuration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u32>,
+    #[serde(rename = "type")]
+    r#type: ThreadType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invitable: Option<bool>,
+}
+
+impl CreateThread {
+    /// A public thread. The name can be up to 100 characters.
+    pub fn new<S: AsRef<str>>(name: S) -> CreateThread {
+        CreateThread {
+            name: name.as_ref().to_string(),
+            auto_archive_duration: None,
+            rate_limit_per_user: None,
+            r#type: ThreadType::Public,
+            invitable: None,
+        }
+    }
+
+    /// Defaults to the channel's default auto archive duration.
+    pub fn auto_archive_duration(mut self, duration: AutoArchiveDuration) -> CreateThread {
+        self.auto_archive_duration = Some(duration);
+        self
+    }
+
+    /// Slowmode, in seconds. Up to 21600.
+    pub fn rate_limit_per_user(mut self, seconds: u32) -> CreateThread {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// Ignored for threads started from a message, which take after the message's channel.
+    pub fn kind(mut self, kind: ThreadType) -> CreateThread {
+        self.r#type = kind;
+        self
+    }
+
+    /// Whether non moderators can add other non moderators to a private thread.
+    pub fn invitable(mut self, invitable: bool) -> CreateThread {
+        self.invitable = Some(invitable);
+        self
+    }
+}
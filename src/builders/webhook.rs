@@ -0,0 +1,150 @@
+use serde::Serialize;
+use crate::builders::attachment::Attachment;
+use crate::builders::message::CreateMessage;
+use crate::model::Snowflake;
+
+/// A webhook to create in a channel.
+#[derive(Serialize, Debug, Clone)]
+pub struct CreateWebhook {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar: Option<String>,
+}
+
+impl CreateWebhook {
+    /// Up to 80 characters, and it can't contain "clyde" or "discord".
+    pub fn new<S: AsRef<str>>(name: S) -> CreateWebhook {
+        CreateWebhook {
+            name: name.as_ref().to_string(),
+            avatar: None,
+        }
+    }
+
+    /// The avatar as a data URI (e.g. `data:image/png;base64,...`).
+    pub fn avatar(mut self, avatar: &str) -> CreateWebhook {
+        self.avatar = Some(avatar.to_string());
+        self
+    }
+}
+
+/// Changes to make to a webhook.
+/// Fields that aren't set are left as they are.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct ModifyWebhook {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Snowflake>,
+}
+
+impl ModifyWebhook {
+    pub fn new() -> ModifyWebhook {
+        ModifyWebhook::default()
+    }
+
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> ModifyWebhook {
+        self.name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// The avatar as a data URI (e.g. `data:image/png;base64,...`), None removes it.
+    pub fn avatar(mut self, avatar: Option<&str>) -> ModifyWebhook {
+        self.avatar = Some(avatar.map(str::to_string));
+        self
+    }
+
+    /// Moves the webhook to another channel of the guild.
+    /// Can't be changed when modifying the webhook with its token.
+    pub fn channel(mut self, channel_id: &Snowflake) -> ModifyWebhook {
+        self.channel_id = Some(channel_id.clone());
+        self
+    }
+}
+
+/// A message to send through a webhook.
+#[derive(Serialize, Debug, Default)]
+pub struct ExecuteWebhook {
+    #[serde(flatten)]
+    message: CreateMessage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_name: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    applied_tags: Vec<Snowflake>,
+    #[serde(skip)]
+    thread_id: Option<Snowflake>,
+}
+
+impl ExecuteWebhook {
+    pub fn new<M: Into<CreateMessage>>(message: M) -> ExecuteWebhook {
+        ExecuteWebhook {
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Overrides the webhook's name for this message.
+    pub fn username<S: AsRef<str>>(mut self, username: S) -> ExecuteWebhook {
+        self.username = Some(username.as_ref().to_string());
+        self
+    }
+
+    /// Overrides the webhook's avatar for this message.
+    pub fn avatar_url<S: AsRef<str>>(mut self, avatar_url: S) -> ExecuteWebhook {
+        self.avatar_url = Some(avatar_url.as_ref().to_string());
+        self
+    }
+
+    /// Sends the message in a thread of the webhook's channel.
+    /// The thread is unarchived if needed.
+    pub fn thread(mut self, thread_id: &Snowflake) -> ExecuteWebhook {
+        self.thread_id = Some(thread_id.clone());
+        self
+    }
+
+    /// Creates a post with this name for the message, for webhooks of forum and media channels.
+    pub fn thread_name<S: AsRef<str>>(mut self, thread_name: S) -> ExecuteWebhook {
+        self.thread_name = Some(thread_name.as_ref().to_string());
+        self
+    }
+
+    /// Applies a tag of the forum or media channel to the post created with `thread_name`.
+    pub fn tag(mut self, tag_id: &Snowflake) -> ExecuteWebhook {
+        self.applied_tags.push(tag_id.clone());
+        self
+    }
+
+    pub(crate) fn thread_id(&self) -> Option<&Snowflake> {
+        self.thread_id.as_ref()
+    }
+
+    /// Splits off the message's files, describing them in the payload's attachments.
+    pub(crate) fn into_payload(mut self) -> (ExecuteWebhook, Vec<Attachment>) {
+        let (message, files) = self.message.into_payload();
+        self.message = message;
+        (self, files)
+    }
+}
+
+impl From<CreateMessage> for ExecuteWebhook {
+    fn from(message: CreateMessage) -> ExecuteWebhook {
+        ExecuteWebhook::new(message)
+    }
+}
+
+impl From<&str> for ExecuteWebhook {
+    fn from(content: &str) -> ExecuteWebhook {
+        ExecuteWebhook::new(content)
+    }
+}
+
+impl From<String> for ExecuteWebhook {
+    fn from(content: String) -> ExecuteWebhook {
+        ExecuteWebhook::new(content)
+    }
+}
@@ -1,13 +1,12 @@
 #![allow(dead_code)]
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use thiserror::Error;
 use rand::prelude::*;
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
-use reqwest::Client;
-use crate::http::QueryError;
+use crate::gateway::compression::GatewayCompression;
+use crate::http::{HttpClient, QueryError};
+use crate::http::retry::RetryPolicy;
 use crate::model::Snowflake;
 use crate::{api, http};
 use crate::model::user::MainUserData;
@@ -22,16 +21,53 @@ pub enum DiscordBuildError {
 
     #[error("ReqwestError: {err}")]
     ReqwestError { err: reqwest::Error },
+
+    #[error("Invalid webhook url: {url}")]
+    InvalidWebhookUrl { url: String },
+}
+
+/// Where the client sends its requests.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// The api's root, without the version.
+    pub api_base: String,
+    pub api_version: u8,
+    /// The gateway's url, without any query parameters.
+    pub gateway_url: String,
+    pub gateway_compression: GatewayCompression,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            api_base: "https://discord.com/api".to_string(),
+            api_version: 9,
+            gateway_url: "wss://gateway.discord.gg".to_string(),
+            gateway_compression: GatewayCompression::default(),
+        }
+    }
+}
+
+impl ClientConfig {
+    /// The versioned api url that all routes are relative to.
+    pub fn api_url(&self) -> String {
+        format!("{}/v{}", self.api_base.trim_end_matches('/'), self.api_version)
+    }
 }
 
 #[derive(Debug)]
 pub struct DiscordClient {
     me: MainUserData,
-    req_client: reqwest::Client,
+    req_client: HttpClient,
+    config: ClientConfig,
 }
 
 impl DiscordClient {
-    pub fn req_client(&self) -> Client {
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    pub fn req_client(&self) -> HttpClient {
         self.req_client.clone()
     }
 
@@ -44,6 +80,9 @@ impl DiscordClient {
 pub struct DiscordClientBuilder {
     auth: String,
     user_agent: String,
+    retry_policy: RetryPolicy,
+    config: ClientConfig,
+    allow_plain_http: bool,
 }
 
 impl DiscordClientBuilder {
@@ -58,13 +97,15 @@ impl DiscordClientBuilder {
 
     /// Builds client for use.
     pub async fn build(self) -> Result<DiscordClient, DiscordBuildError> {
-        let req_client = http::build_request_client(&self.auth, &self.user_agent)
+        let req_client = http::build_request_client(&self.auth, &self.user_agent, !self.allow_plain_http)
+        .map(|client| HttpClient::new(client, self.config.api_url(), self.retry_policy))
         .map_err(|e| DiscordBuildError::ReqwestError { err: e })?;
 
         Ok(
             DiscordClient {
                 me: api::get_authenticated_user_data(req_client.clone()).await.map_err(|e| DiscordBuildError::QueryError { err: e })?,
                 req_client,
+                config: self.config,
             }
         )
     }
@@ -75,6 +116,49 @@ impl DiscordClientBuilder {
         self
     }
 
+    /// Set's the policy used to retry rate limited and failed requests.
+    /// By default, requests are sent up to 3 times.
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> DiscordClientBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set's the root of the api, without the version.
+    /// Defaults to `https://discord.com/api`.
+    pub fn set_api_base(mut self, api_base: &str) -> DiscordClientBuilder {
+        self.config.api_base = api_base.to_string();
+        self
+    }
+
+    /// Set's the api version used for requests and the gateway.
+    /// Defaults to 9.
+    pub fn set_api_version(mut self, api_version: u8) -> DiscordClientBuilder {
+        self.config.api_version = api_version;
+        self
+    }
+
+    /// Set's the gateway url, without any query parameters.
+    /// Defaults to `wss://gateway.discord.gg`.
+    pub fn set_gateway_url(mut self, gateway_url: &str) -> DiscordClientBuilder {
+        self.config.gateway_url = gateway_url.to_string();
+        self
+    }
+
+    /// Set's how the gateway compresses its messages.
+    /// Defaults to no compression.
+    pub fn set_gateway_compression(mut self, compression: GatewayCompression) -> DiscordClientBuilder {
+        self.config.gateway_compression = compression;
+        self
+    }
+
+    /// Allows requests over plain http, so the client can be pointed at a local stand-in server.
+    /// Only available for tests.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn allow_plain_http(mut self) -> DiscordClientBuilder {
+        self.allow_plain_http = true;
+        self
+    }
+
     /// Set's the user agent for the client to a random agent.
     /// Selected from a list of 100 agents.
     pub fn set_random_agent(mut self, seed: u64) -> DiscordClientBuilder {
@@ -188,4 +272,45 @@ const AGENTS: &[&str] = &[
     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_9_5) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/44.0.2403.157 Safari/537.36",
     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_10_1) AppleWebKit/600.1.25 (KHTML, like Gecko) Version/8.0 Safari/600.1.25",
     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.10; rv:39.0) Gecko/20100101 Firefox/39.0",
-];
\ No newline at end of file
+];
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+    use crate::http::mock::{MockApi, ME};
+    use crate::http::QueryError;
+    use super::*;
+
+    #[tokio::test]
+    async fn requests_go_to_the_configured_api() {
+        let api = MockApi::start([ME.to_string()]).await;
+        let addr = api.addr();
+
+        let client = DiscordClientBuilder::new("token")
+            .set_api_base(&format!("http://{addr}/custom/api/"))
+            .set_api_version(10)
+            .allow_plain_http()
+            .build().await
+            .unwrap();
+
+        assert_eq!(api.requests(), ["GET /custom/api/v10/users/@me"]);
+        assert_eq!(client.req_client().api_url(), format!("http://{addr}/custom/api/v10"));
+        assert_eq!(client.user_id(), &Snowflake::new("80351110224678912"));
+    }
+
+    #[tokio::test]
+    async fn plain_http_is_refused_by_default() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let err = DiscordClientBuilder::new("token")
+            .set_api_base(&format!("http://{addr}/api"))
+            .set_retry_policy(RetryPolicy::never())
+            .build().await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            DiscordBuildError::QueryError { err: QueryError::ReqwestError { ref err, .. } } if err.is_builder()
+        ), "{err}");
+    }
+}
@@ -1,15 +1,100 @@
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use crate::model::guild::audit_log::EventType;
+use crate::model::message::{ReactionEmoji, ReactionType};
 use crate::model::Snowflake;
 
-pub const ME: &str = "https://discord.com/api/v9/users/@me";
-pub const PRIVATE_CHANNELS: &str = "https://discord.com/api/v9/users/@me/channels";
-pub const GUILDS: &str = "https://discord.com/api/v9/users/@me/guilds";
+// Routes are relative to the client's api url, see `ClientConfig`.
+pub const ME: &str = "/users/@me";
+pub const PRIVATE_CHANNELS: &str = "/users/@me/channels";
+pub const GUILDS: &str = "/users/@me/guilds";
 
 pub fn channel(channel_id: &Snowflake) -> String {
-    format!("https://discord.com/api/v9/channels/{}", channel_id)
+    format!("/channels/{}", channel_id)
+}
+
+pub fn guild(guild_id: &Snowflake, with_counts: bool) -> String {
+    format!("/guilds/{guild_id}?with_counts={with_counts}")
+}
+
+pub fn modify_guild(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}")
+}
+
+pub fn guild_preview(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/preview")
+}
+
+pub fn roles(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/roles")
+}
+
+pub fn role(guild_id: &Snowflake, role_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/roles/{role_id}")
+}
+
+pub fn members(guild_id: &Snowflake, after_user_id: Option<&Snowflake>, limit: u16) -> String {
+    match after_user_id {
+        Some(after) => format!("/guilds/{guild_id}/members?after={after}&limit={limit}"),
+        None => format!("/guilds/{guild_id}/members?limit={limit}")
+    }
+}
+
+pub fn search_members(guild_id: &Snowflake, query: &str, limit: u16) -> String {
+    let query = utf8_percent_encode(query, NON_ALPHANUMERIC);
+    format!("/guilds/{guild_id}/members/search?query={query}&limit={limit}")
+}
+
+pub fn member(guild_id: &Snowflake, user_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/members/{user_id}")
+}
+
+pub fn bans(guild_id: &Snowflake, after_user_id: Option<&Snowflake>, limit: u16) -> String {
+    match after_user_id {
+        Some(after) => format!("/guilds/{guild_id}/bans?after={after}&limit={limit}"),
+        None => format!("/guilds/{guild_id}/bans?limit={limit}")
+    }
+}
+
+pub fn ban(guild_id: &Snowflake, user_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/bans/{user_id}")
+}
+
+pub fn bulk_ban(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/bulk-ban")
+}
+
+pub fn member_role(guild_id: &Snowflake, user_id: &Snowflake, role_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/members/{user_id}/roles/{role_id}")
+}
+
+pub fn audit_log(
+    guild_id: &Snowflake,
+    user_id: Option<&Snowflake>,
+    action_type: Option<EventType>,
+    before: Option<&Snowflake>,
+    after: Option<&Snowflake>,
+    limit: u8
+) -> String {
+    let mut route = format!("/guilds/{guild_id}/audit-logs?limit={limit}");
+    if let Some(user_id) = user_id {
+        route.push_str(&format!("&user_id={user_id}"));
+    }
+    if let Some(action_type) = action_type {
+        route.push_str(&format!("&action_type={}", action_type as u16));
+    }
+    if let Some(before) = before {
+        route.push_str(&format!("&before={before}"));
+    }
+    if let Some(after) = after {
+        route.push_str(&format!("&after={after}"));
+    }
+    route
 }
 
 pub fn guild_channels(guild_id: &Snowflake) -> String {
-    format!("https://discord.com/api/v9/guilds/{}/channels", guild_id)
+    format!("/guilds/{}/channels", guild_id)
 }
 
 pub fn send_message(channel_id: &Snowflake) -> String {
@@ -21,16 +106,210 @@ pub fn start_typing(channel_id: &Snowflake) -> String {
 }
 
 pub fn user(user_id: &Snowflake) -> String {
-    format!("https://discord.com/api/v9/users/{}", user_id)
+    format!("/users/{}", user_id)
 }
 
 pub fn messages(channel_id: &Snowflake, before_message_id: Option<&Snowflake>, limit: u8) -> String {
     match before_message_id {
-        Some(before_msg_id) => format!("https://discord.com/api/v9/channels/{channel_id}/messages?before={before_msg_id}&limit={limit}"),
-        None => format!("https://discord.com/api/v9/channels/{channel_id}/messages?&limit={limit}")
+        Some(before_msg_id) => format!("/channels/{channel_id}/messages?before={before_msg_id}&limit={limit}"),
+        None => format!("/channels/{channel_id}/messages?&limit={limit}")
     }
 }
 
 pub fn message(channel_id: &Snowflake, message_id: &Snowflake) -> String {
-    format!("https://discord.com/api/v9/channels/{channel_id}/messages/{message_id}")
-}
\ No newline at end of file
+    format!("/channels/{channel_id}/messages/{message_id}")
+}
+
+pub fn bulk_delete_messages(channel_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/messages/bulk-delete")
+}
+
+pub fn crosspost_message(channel_id: &Snowflake, message_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/messages/{message_id}/crosspost")
+}
+
+pub fn reactions(channel_id: &Snowflake, message_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/messages/{message_id}/reactions")
+}
+
+pub fn emoji_reactions(channel_id: &Snowflake, message_id: &Snowflake, emoji: &ReactionEmoji) -> String {
+    format!("{}/{}", reactions(channel_id, message_id), emoji.route_segment())
+}
+
+/// `user_id` is None for the current user.
+pub fn user_reaction(channel_id: &Snowflake, message_id: &Snowflake, emoji: &ReactionEmoji, user_id: Option<&Snowflake>) -> String {
+    match user_id {
+        Some(user_id) => format!("{}/{user_id}", emoji_reactions(channel_id, message_id, emoji)),
+        None => format!("{}/@me", emoji_reactions(channel_id, message_id, emoji)),
+    }
+}
+
+pub fn reactors(channel_id: &Snowflake, message_id: &Snowflake, emoji: &ReactionEmoji, reaction_type: ReactionType, after_user_id: Option<&Snowflake>, limit: u8) -> String {
+    let route = emoji_reactions(channel_id, message_id, emoji);
+    let reaction_type = reaction_type as u8;
+    match after_user_id {
+        Some(after) => format!("{route}?type={reaction_type}&after={after}&limit={limit}"),
+        None => format!("{route}?type={reaction_type}&limit={limit}")
+    }
+}
+
+pub fn pinned_messages(channel_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/pins")
+}
+
+/// Formats a timestamp to be used in a query string.
+fn timestamp_query(timestamp: OffsetDateTime) -> Option<String> {
+    timestamp.format(&Rfc3339).ok()
+        .map(|timestamp| utf8_percent_encode(&timestamp, NON_ALPHANUMERIC).to_string())
+}
+
+pub fn pins(channel_id: &Snowflake, before: Option<OffsetDateTime>, limit: u8) -> String {
+    match before.and_then(timestamp_query) {
+        Some(before) => format!("/channels/{channel_id}/messages/pins?before={before}&limit={limit}"),
+        None => format!("/channels/{channel_id}/messages/pins?limit={limit}")
+    }
+}
+
+pub fn pin(channel_id: &Snowflake, message_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/messages/pins/{message_id}")
+}
+
+pub fn message_threads(channel_id: &Snowflake, message_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/messages/{message_id}/threads")
+}
+
+pub fn threads(channel_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/threads")
+}
+
+/// `user_id` is None for the current user.
+pub fn thread_member(thread_id: &Snowflake, user_id: Option<&Snowflake>) -> String {
+    match user_id {
+        Some(user_id) => format!("/channels/{thread_id}/thread-members/{user_id}"),
+        None => format!("/channels/{thread_id}/thread-members/@me")
+    }
+}
+
+pub fn active_threads(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/threads/active")
+}
+
+pub fn public_archived_threads(channel_id: &Snowflake, before: Option<OffsetDateTime>, limit: u8) -> String {
+    match before.and_then(timestamp_query) {
+        Some(before) => format!("/channels/{channel_id}/threads/archived/public?before={before}&limit={limit}"),
+        None => format!("/channels/{channel_id}/threads/archived/public?limit={limit}")
+    }
+}
+
+pub fn private_archived_threads(channel_id: &Snowflake, before: Option<OffsetDateTime>, limit: u8) -> String {
+    match before.and_then(timestamp_query) {
+        Some(before) => format!("/channels/{channel_id}/threads/archived/private?before={before}&limit={limit}"),
+        None => format!("/channels/{channel_id}/threads/archived/private?limit={limit}")
+    }
+}
+
+pub fn joined_private_archived_threads(channel_id: &Snowflake, before_thread_id: Option<&Snowflake>, limit: u8) -> String {
+    match before_thread_id {
+        Some(before) => format!("/channels/{channel_id}/users/@me/threads/archived/private?before={before}&limit={limit}"),
+        None => format!("/channels/{channel_id}/users/@me/threads/archived/private?limit={limit}")
+    }
+}
+
+pub fn channel_invites(channel_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/invites")
+}
+
+pub fn guild_invites(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/invites")
+}
+
+pub fn invite(code: &str) -> String {
+    format!("/invites/{}", utf8_percent_encode(code, NON_ALPHANUMERIC))
+}
+
+pub fn resolve_invite(code: &str, with_counts: bool) -> String {
+    format!("{}?with_counts={with_counts}", invite(code))
+}
+
+pub fn channel_webhooks(channel_id: &Snowflake) -> String {
+    format!("/channels/{channel_id}/webhooks")
+}
+
+pub fn guild_webhooks(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/webhooks")
+}
+
+pub fn webhook(webhook_id: &Snowflake) -> String {
+    format!("/webhooks/{webhook_id}")
+}
+
+/// Tokens from webhook urls aren't checked, so they're escaped to stay in their path segment.
+fn token_segment(token: &str) -> String {
+    utf8_percent_encode(token, NON_ALPHANUMERIC).to_string()
+}
+
+pub fn webhook_with_token(webhook_id: &Snowflake, token: &str) -> String {
+    let token = token_segment(token);
+    format!("/webhooks/{webhook_id}/{token}")
+}
+
+pub fn execute_webhook(webhook_id: &Snowflake, token: &str, wait: bool, thread_id: Option<&Snowflake>) -> String {
+    let token = token_segment(token);
+    match thread_id {
+        Some(thread_id) => format!("/webhooks/{webhook_id}/{token}?wait={wait}&thread_id={thread_id}"),
+        None => format!("/webhooks/{webhook_id}/{token}?wait={wait}")
+    }
+}
+
+pub fn webhook_message(webhook_id: &Snowflake, token: &str, message_id: &Snowflake, thread_id: Option<&Snowflake>) -> String {
+    let token = token_segment(token);
+    match thread_id {
+        Some(thread_id) => format!("/webhooks/{webhook_id}/{token}/messages/{message_id}?thread_id={thread_id}"),
+        None => format!("/webhooks/{webhook_id}/{token}/messages/{message_id}")
+    }
+}
+
+pub fn guild_emojis(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/emojis")
+}
+
+pub fn guild_emoji(guild_id: &Snowflake, emoji_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/emojis/{emoji_id}")
+}
+
+pub fn guild_stickers(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/stickers")
+}
+
+pub fn guild_sticker(guild_id: &Snowflake, sticker_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/stickers/{sticker_id}")
+}
+
+pub fn scheduled_events(guild_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/scheduled-events")
+}
+
+pub fn list_scheduled_events(guild_id: &Snowflake, with_user_count: bool) -> String {
+    format!("{}?with_user_count={with_user_count}", scheduled_events(guild_id))
+}
+
+pub fn scheduled_event(guild_id: &Snowflake, event_id: &Snowflake) -> String {
+    format!("/guilds/{guild_id}/scheduled-events/{event_id}")
+}
+
+pub fn get_scheduled_event(guild_id: &Snowflake, event_id: &Snowflake, with_user_count: bool) -> String {
+    format!("{}?with_user_count={with_user_count}", scheduled_event(guild_id, event_id))
+}
+
+pub fn scheduled_event_users(
+    guild_id: &Snowflake,
+    event_id: &Snowflake,
+    after_user_id: Option<&Snowflake>,
+    limit: u8,
+    with_member: bool
+) -> String {
+    match after_user_id {
+        Some(after) => format!("/guilds/{guild_id}/scheduled-events/{event_id}/users?after={after}&limit={limit}&with_member={with_member}"),
+        None => format!("/guilds/{guild_id}/scheduled-events/{event_id}/users?limit={limit}&with_member={with_member}")
+    }
+}
@@ -0,0 +1,182 @@
+use flate2::{Decompress, FlushDecompress, Status};
+use super::error::GatewayError;
+
+/// How the gateway compresses the messages it sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GatewayCompression {
+    /// Plain json text messages.
+    #[default]
+    None,
+    /// The whole connection is one zlib stream, split over binary messages.
+    ZlibStream,
+    /// The whole connection is one zstd stream, flushed at the end of every message.
+    #[cfg(feature = "zstd")]
+    ZstdStream,
+}
+
+impl GatewayCompression {
+    /// The value of the `compress` query parameter when connecting.
+    pub(crate) fn query_param(&self) -> Option<&'static str> {
+        match self {
+            GatewayCompression::None => None,
+            GatewayCompression::ZlibStream => Some("zlib-stream"),
+            #[cfg(feature = "zstd")]
+            GatewayCompression::ZstdStream => Some("zstd-stream"),
+        }
+    }
+}
+
+/// Decompresses the binary messages of a single connection.
+pub(crate) enum StreamDecoder {
+    Zlib(ZlibStreamDecoder),
+    #[cfg(feature = "zstd")]
+    Zstd(ZstdStreamDecoder),
+}
+
+impl StreamDecoder {
+    /// None when the connection isn't compressed.
+    pub(crate) fn new(compression: GatewayCompression) -> Result<Option<StreamDecoder>, GatewayError> {
+        Ok(match compression {
+            GatewayCompression::None => None,
+            GatewayCompression::ZlibStream => Some(StreamDecoder::Zlib(ZlibStreamDecoder::new())),
+            #[cfg(feature = "zstd")]
+            GatewayCompression::ZstdStream => Some(StreamDecoder::Zstd(ZstdStreamDecoder::new()?)),
+        })
+    }
+
+    /// Returns the payload once all of its messages were received.
+    pub(crate) fn push(&mut self, message: &[u8]) -> Result<Option<String>, GatewayError> {
+        match self {
+            StreamDecoder::Zlib(zlib) => zlib.push(message),
+            #[cfg(feature = "zstd")]
+            StreamDecoder::Zstd(zstd) => zstd.push(message).map(Some),
+        }
+    }
+}
+
+/// Every complete zlib-stream payload ends with this flush marker.
+const ZLIB_SUFFIX: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// Inflates the messages of a `zlib-stream` connection.
+/// The inflate context is shared by the whole connection, so one decoder
+/// must see every binary message of a connection, in order.
+pub struct ZlibStreamDecoder {
+    inflate: Decompress,
+    /// Compressed data of a payload split over several messages.
+    buffer: Vec<u8>,
+}
+
+impl Default for ZlibStreamDecoder {
+    fn default() -> Self {
+        ZlibStreamDecoder::new()
+    }
+}
+
+impl ZlibStreamDecoder {
+    pub fn new() -> ZlibStreamDecoder {
+        ZlibStreamDecoder {
+            inflate: Decompress::new(true),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Takes a binary message, returns the payload once all of its messages were received.
+    pub fn push(&mut self, message: &[u8]) -> Result<Option<String>, GatewayError> {
+        self.buffer.extend_from_slice(message);
+        if !self.buffer.ends_with(&ZLIB_SUFFIX) {
+            return Ok(None);
+        }
+
+        let compressed = std::mem::take(&mut self.buffer);
+        let result = inflate(&mut self.inflate, &compressed);
+        // Keeps the allocation around for the next payload.
+        self.buffer = compressed;
+        self.buffer.clear();
+        result.map(Some)
+    }
+}
+
+fn inflate(inflate: &mut Decompress, mut input: &[u8]) -> Result<String, GatewayError> {
+    let mut output = Vec::with_capacity(input.len() * 4);
+    loop {
+        if output.len() == output.capacity() {
+            output.reserve(output.capacity().max(1024));
+        }
+
+        let (total_in, total_out) = (inflate.total_in(), inflate.total_out());
+        let status = inflate.decompress_vec(input, &mut output, FlushDecompress::Sync)
+            .map_err(|e| GatewayError::DeserializeError { err: format!("zlib-stream: {e}") })?;
+        let consumed = (inflate.total_in() - total_in) as usize;
+        let produced = inflate.total_out() - total_out;
+        input = &input[consumed..];
+
+        // Everything was inflated once the inflater stops short of filling the output.
+        if status == Status::StreamEnd || (input.is_empty() && output.len() < output.capacity()) {
+            break;
+        }
+        if consumed == 0 && produced == 0 && output.len() < output.capacity() {
+            return Err(GatewayError::DeserializeError { err: "zlib-stream: corrupt payload".to_string() });
+        }
+    }
+
+    String::from_utf8(output)
+        .map_err(|e| GatewayError::DeserializeError { err: format!("zlib-stream: {e}") })
+}
+
+/// Frames needing a window larger than 2^23 bytes (8 MiB) are refused by default.
+#[cfg(feature = "zstd")]
+pub const DEFAULT_ZSTD_WINDOW_LOG_MAX: u32 = 23;
+
+/// Decompresses the messages of a `zstd-stream` connection.
+/// Like zlib-stream, the decoder context is shared by the whole connection,
+/// but every message holds a complete payload.
+#[cfg(feature = "zstd")]
+pub struct ZstdStreamDecoder {
+    decoder: zstd::stream::raw::Decoder<'static>,
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdStreamDecoder {
+    pub fn new() -> Result<ZstdStreamDecoder, GatewayError> {
+        ZstdStreamDecoder::with_window_log_max(DEFAULT_ZSTD_WINDOW_LOG_MAX)
+    }
+
+    /// Bounds the memory the decoder uses to a window of 2^`window_log_max` bytes,
+    /// frames that need a larger one are refused.
+    pub fn with_window_log_max(window_log_max: u32) -> Result<ZstdStreamDecoder, GatewayError> {
+        use zstd::stream::raw::DParameter;
+
+        let mut decoder = zstd::stream::raw::Decoder::new()
+            .map_err(|e| GatewayError::Custom { text: format!("zstd-stream: {e}") })?;
+        decoder.set_parameter(DParameter::WindowLogMax(window_log_max))
+            .map_err(|e| GatewayError::Custom { text: format!("zstd-stream: {e}") })?;
+
+        Ok(ZstdStreamDecoder { decoder })
+    }
+
+    /// Takes a binary message and returns its payload.
+    pub fn push(&mut self, message: &[u8]) -> Result<String, GatewayError> {
+        use zstd::stream::raw::{InBuffer, Operation, OutBuffer};
+
+        let mut input = InBuffer::around(message);
+        let mut output: Vec<u8> = Vec::with_capacity((message.len() * 8).max(1024));
+        loop {
+            if output.len() == output.capacity() {
+                output.reserve(output.capacity());
+            }
+
+            let written = output.len();
+            let mut buffer = OutBuffer::around_pos(&mut output, written);
+            self.decoder.run(&mut input, &mut buffer)
+                .map_err(|e| GatewayError::DeserializeError { err: format!("zstd-stream: {e}") })?;
+
+            // Everything was decompressed once the decoder stops short of filling the output.
+            if input.pos() == message.len() && output.len() < output.capacity() {
+                break;
+            }
+        }
+
+        String::from_utf8(output)
+            .map_err(|e| GatewayError::DeserializeError { err: format!("zstd-stream: {e}") })
+    }
+}
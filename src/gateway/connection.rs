@@ -1,25 +1,32 @@
 #![allow(dead_code)]
 
-use serde_json::Number;
-use tokio::sync::mpsc;
-use tokio::sync::mpsc::Receiver;
+use rand::Rng;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, watch};
+use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::task::JoinHandle;
+use tokio::time::{interval_at, sleep, Instant, Interval, MissedTickBehavior};
 use serde::Deserialize;
 use serde_json::Value;
 use tokio_stream::wrappers::ReceiverStream;
-use tokio_tungstenite::tungstenite::Message;
-use tokio::time::sleep;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use serde::Serialize;
-use futures_util::{future, pin_mut, SinkExt, StreamExt};
-use std::collections::HashMap;
-use std::collections::VecDeque;
-use std::sync::Arc;
-use std::sync::Mutex;
+use futures_util::stream::SplitSink;
+use futures_util::{future, SinkExt, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 use std::time::Duration;
 use anyhow::Result;
 use futures_util::Stream;
+use crate::client::ClientConfig;
+use super::compression::{GatewayCompression, StreamDecoder};
 use super::dispatched_event::DispatchedEvent;
-use super::error::GatewayError;
+use super::error::{GatewayCloseCode, GatewayError};
 use super::events::*;
 
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
@@ -52,196 +59,496 @@ struct ClientState {
     guild_versions: HashMap<String, u32>,
 }
 
+/// How many connections in a row may fail or end before the session is ready, before giving up.
+const MAX_CONNECT_ATTEMPTS: u32 = 10;
+
+/// The connection to the gateway, a stream of the events it dispatches.
 pub struct GatewayConnection {
-    curr_sequence: Arc<Mutex<u64>>,
-    // will be 0 when heartbeats shouldn't be sent. 
-    heartbeat_interval_ms: Arc<Mutex<u64>>,
-    ws_data_read_loop: JoinHandle<()>,
-    ws_heartbeat_send_loop: JoinHandle<()>,
-    event_receiver: Receiver<DispatchedEvent>
+    runner: JoinHandle<()>,
+    event_receiver: Receiver<GatewayResult<DispatchedEvent>>,
+    latency: Arc<Mutex<LatencyTracker>>,
+    status: watch::Receiver<GatewayStatus>,
 }
 
 impl GatewayConnection { 
-    // TODO! a rewrite is in order...
     pub async fn new(token: &str) -> Result<GatewayConnection> {
-        // TODO! "wss://gateway.discord.gg/?encoding=etf&v=9&compress=zstd-stream"
-        let ws = "wss://gateway.discord.gg/?encoding=json&v=9";
-        let (ws_stream, _) = tokio_tungstenite::connect_async(ws).await?;
+        Self::with_config(token, &ClientConfig::default()).await
+    }
 
+    /// Connects to the gateway url and api version in the client's config.
+    /// The connection resumes its session whenever it drops, or identifies a new one
+    /// if it can't be resumed, until the events are dropped.
+    pub async fn with_config(token: &str, config: &ClientConfig) -> Result<GatewayConnection> {
         let (event_sender, event_receiver) = mpsc::channel(256); 
-        let (mut write, read) = ws_stream.split();
-        
-        let properties = Properties {
-            os: "Windows".to_string(),
-            browser: "Firefox".to_string(),
-            // release_channel: "stable".to_string(),
-            // client_version: "1.0.9151".to_string(),
-            // os_version: "10.0.19045".to_string(),
-            system_locale: "en-US".to_string(),
-            browser_user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) discord/1.0.9151 Chrome/120.0.6099.291 Electron/28.2.10 Safari/537.36".to_string(),
-            // browser_version: "28.2.10".to_string(),
-            // client_build_number: 304683,
-            // native_build_number: 48891,
-            // client_event_source: None,
-            // design_id: 0,
-        };
+        let (status_sender, status) = watch::channel(GatewayStatus::Identifying);
+        let latency = Arc::new(Mutex::new(LatencyTracker::default()));
 
-        let presence = Presence {
-            status: "unknown".to_string(),
-            since: 0,
-            activities: vec![],
-            afk: false,
-            broadcast: None,
+        let session = GatewaySession {
+            token: token.to_string(),
+            gateway_url: config.gateway_url.trim_end_matches('/').to_string(),
+            api_version: config.api_version,
+            compression: config.gateway_compression,
+            resume: None,
+            sequence: None,
+            event_sender,
+            latency: latency.clone(),
+            status: status_sender,
         };
+        let ws = session.connect().await?;
+
+        Ok(GatewayConnection {
+            runner: tokio::spawn(session.run(ws)),
+            event_receiver,
+            latency,
+            status,
+        })
+    }
+
+    /// How long the gateway takes to acknowledge heartbeats.
+    pub fn latency(&self) -> GatewayLatency {
+        self.latency.lock().unwrap().latency()
+    }
+
+    pub fn status(&self) -> GatewayStatus {
+        *self.status.borrow()
+    }
+
+    /// Notified whenever the connection reconnects, or ends.
+    /// Has to be taken before turning the connection into its events.
+    pub fn status_changes(&self) -> watch::Receiver<GatewayStatus> {
+        self.status.clone()
+    }
+
+    /// Ends with `GatewayError::Closed` if the gateway closes the connection for good,
+    /// or `GatewayError::ConnectionFailed` if it can't be reached anymore.
+    /// Events that can't be deserialized come as `GatewayError::DeserializeError`, the stream goes on after them.
+    pub fn events(self) -> impl Stream<Item = GatewayResult<DispatchedEvent>> {
+        // Create a new stream type that wraps the receiver
+        ReceiverStream::new(self.event_receiver)
+    }
+    
+    pub async fn wait_until_finish(self) -> GatewayResult<()> {
+        self.runner.await
+            .map_err(|e| GatewayError::TaskFailed { err: e.to_string() })
+    }
+}
+
+impl Stream for GatewayConnection {
+    type Item = GatewayResult<DispatchedEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.event_receiver.poll_recv(cx)
+    }
+}
+
+/// What the connection is doing, the gateway's own events aside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatewayStatus {
+    /// Identifying a new session, events dispatched before it's ready are lost.
+    Identifying,
+    /// Reconnecting to resume the session, the events missed in between are sent once it's resumed.
+    Resuming,
+    /// The session is ready or resumed, events are coming in.
+    Connected,
+    /// The connection ended for good.
+    Disconnected,
+}
+
+/// Round-trip times between heartbeats and their acknowledgements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GatewayLatency {
+    /// None until the first heartbeat is acknowledged.
+    pub last: Option<Duration>,
+    /// The average of the last few heartbeats.
+    pub average: Option<Duration>,
+}
+
+/// How many heartbeats the average latency is measured over.
+const LATENCY_SAMPLES: usize = 10;
 
-        let client_state = ClientState {
-            guild_versions: std::collections::HashMap::new(),
+#[derive(Debug, Default)]
+struct LatencyTracker {
+    samples: VecDeque<Duration>,
+}
+
+impl LatencyTracker {
+    fn record(&mut self, latency: Duration) {
+        if self.samples.len() == LATENCY_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    fn latency(&self) -> GatewayLatency {
+        let average = match self.samples.len() {
+            0 => None,
+            len => Some(self.samples.iter().sum::<Duration>() / len as u32),
         };
+        GatewayLatency {
+            last: self.samples.back().copied(),
+            average,
+        }
+    }
+}
+
+/// The heartbeats of a single connection.
+struct Heartbeat {
+    interval: Interval,
+    /// When the heartbeat that hasn't been acknowledged yet was sent.
+    unacknowledged_since: Option<Instant>,
+}
 
-        let gateway_login = GatewaySendEventRaw::login(
-            token.to_string(),
-            30717,
-            properties,
-            presence,
-            false,
-            client_state,
-        )?;
+impl Heartbeat {
+    /// The first heartbeat is sent after a random part of the interval,
+    /// so clients that connected together don't all beat at once.
+    /// Heartbeats missed while the connection was busy, e.g. waiting on a slow consumer of the events,
+    /// aren't sent in a burst, which would find the last one unacknowledged and drop the connection.
+    fn new(period: Duration) -> Heartbeat {
+        let jitter = period.mul_f64(rand::thread_rng().gen::<f64>());
+        let mut interval = interval_at(Instant::now() + jitter, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Heartbeat {
+            interval,
+            unacknowledged_since: None,
+        }
+    }
+}
 
-        let gateway_login_json = serde_json::to_value(&gateway_login)?;
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type GatewayResult<T> = std::result::Result<T, GatewayError>;
 
-        write.send(Message::Text(gateway_login_json.to_string())).await?;
+/// Where and how to resume the session, from the `Ready` event.
+struct ResumeInfo {
+    session_id: String,
+    resume_gateway_url: String,
+}
 
-        let curr_sequence = Arc::new(Mutex::new(0));
-        let heartbeat_interval_ms = Arc::new(Mutex::new(0));
+/// Why a connection ended.
+enum ConnectionEnd {
+    /// Reconnect and resume the session.
+    Resume,
+    /// Reconnect with a new session.
+    Identify,
+    /// Nobody is listening to the events anymore.
+    Stop,
+    /// The gateway closed the connection and reconnecting wouldn't help.
+    Closed { code: GatewayCloseCode, reason: String },
+}
 
-        let connection = GatewayConnection {
-            curr_sequence: curr_sequence.clone(),
-            heartbeat_interval_ms: heartbeat_interval_ms.clone(),
-            event_receiver,
-            ws_data_read_loop: {
-                let heartbeat_interval = heartbeat_interval_ms.clone();
-                let curr_sequence = curr_sequence.clone();
-                let read_task = tokio::spawn(
-                    read.for_each(move |message| {
-                    let heartbeat_interval = heartbeat_interval.clone();
-                    let curr_sequence = curr_sequence.clone();
-                    let event_sender = event_sender.clone();
-                    async move {
-                        let recv_data = message.unwrap().into_text().unwrap();
-                        if recv_data.is_empty() {
-                            println!("Data is empty.. returning..");
-                            return;
-                        }
-                        let json: Value = serde_json::from_str(
-                            &recv_data
-                        ).unwrap();
-
-                        let sequence = json.get("s").unwrap_or(&Value::Number(Number::from(0)))
-                            .as_u64().unwrap_or_default();
-                        {
-                            let mut curr_sequence = curr_sequence.lock().unwrap();
-                            *curr_sequence = sequence;
-                        }
-                        let recv_event = GatewayRecieveEvent::deserialize(json);
-                        match recv_event {
-                            Ok(e) => {
-                                match e {
-                                    // Heartbeats are handled automatically.
-                                    GatewayRecieveEvent::Hello { heartbeat_info } => {
-                                        // update_sequence(&common);
-                                        let mut heartbeat_interval = heartbeat_interval.lock().unwrap();
-                                        *heartbeat_interval = heartbeat_info.heartbeat_interval;
-                                    },
-                                    // TODO! be sure to handle the RESUME event, as it sends a list of events
-                                    // the only events that the user should be notified about.
-                                    GatewayRecieveEvent::GeneralEvent { dispatched_event } => {
-                                        // update_sequence(&common);
-                                        drop(curr_sequence);
-                                        event_sender.send(dispatched_event).await;
-                                    }
-                                    GatewayRecieveEvent::HeartbeatAck {  } => {
-                                        // update_sequence(&common);
-                                    },
-                                    GatewayRecieveEvent::UnwantedEvent {  } => {
-                                        // update_sequence(&common);
-                                    },
-                                };
-                            },
-                            Err(e) => {
-                                // why must i do this chat
-                                if e.is_data() && e.to_string().contains("UnwantedEventError") {
-                                    eprintln!("Ignoring unwanted event..");
-                                }
-                                // eprintln!("Recieved unwanted event type: {event_name}")
-                            },
-                        }
+/// The session shared by the successive connections to the gateway.
+struct GatewaySession {
+    token: String,
+    gateway_url: String,
+    api_version: u8,
+    compression: GatewayCompression,
+    resume: Option<ResumeInfo>,
+    /// The sequence of the last dispatched event.
+    sequence: Option<u64>,
+    event_sender: Sender<GatewayResult<DispatchedEvent>>,
+    latency: Arc<Mutex<LatencyTracker>>,
+    status: watch::Sender<GatewayStatus>,
+}
+
+impl GatewaySession {
+    /// Connects to the resume url when there is a session to resume.
+    async fn connect(&self) -> Result<WsStream> {
+        let url = match &self.resume {
+            Some(resume) => resume.resume_gateway_url.trim_end_matches('/'),
+            None => &self.gateway_url,
+        };
+        let mut url = format!("{url}/?encoding=json&v={}", self.api_version);
+        if let Some(compress) = self.compression.query_param() {
+            url.push_str("&compress=");
+            url.push_str(compress);
+        }
+        let (ws_stream, _) = tokio_tungstenite::connect_async(url).await?;
+        Ok(ws_stream)
+    }
+
+    async fn run(mut self, ws: WsStream) {
+        self.reconnect_loop(ws).await;
+        self.status.send_replace(GatewayStatus::Disconnected);
+    }
+
+    async fn reconnect_loop(&mut self, ws: WsStream) {
+        let mut ws = Some(ws);
+        // Connections in a row that ended before the session was ready or resumed.
+        let mut attempts: u32 = 0;
+
+        loop {
+            attempts += 1;
+            let connection = match ws.take() {
+                Some(ws) => Ok(ws),
+                None => self.connect().await,
+            };
+            let end = match connection {
+                Ok(ws) => self.run_connection(ws, &mut attempts).await,
+                Err(e) => Err(e),
+            };
+
+            let failure = match end {
+                Ok(ConnectionEnd::Resume) => {
+                    self.status.send_replace(GatewayStatus::Resuming);
+                    None
+                },
+                Ok(ConnectionEnd::Identify) => {
+                    self.resume = None;
+                    self.sequence = None;
+                    self.status.send_replace(GatewayStatus::Identifying);
+                    None
+                },
+                Ok(ConnectionEnd::Stop) => return,
+                Ok(ConnectionEnd::Closed { code, reason }) => {
+                    let _ = self.event_sender.send(Err(GatewayError::Closed { code, reason })).await;
+                    return;
+                },
+                Err(e) => {
+                    if !is_transient(&e) {
+                        let err = GatewayError::ConnectionFailed { err: e.to_string(), attempts };
+                        let _ = self.event_sender.send(Err(err)).await;
+                        return;
                     }
-                    })
-                );
-        
-                read_task
-            },
-            ws_heartbeat_send_loop: {
-                let heartbeat_interval = heartbeat_interval_ms.clone();
-                let curr_sequence = curr_sequence.clone();
-                let heartbeat_task = tokio::spawn(async move {
-                    'send_loop: loop {
-                        // TODO! check if a heartbeat is sent immediately after sending the login data. 
-                        let ms = match heartbeat_interval.lock() {
-                            Ok(ms) => *ms,
-                            Err(poison) => {
-                                eprintln!("Websocket read thread has panicked, no longer sending heartbeats.");
-                                break 'send_loop;
-                            }
-                        };
-
-                        if ms != 0 {
-                            let heartbeat_payload = {
-                                let curr_sequence = curr_sequence.lock().unwrap();
-                                serde_json::json!({
-                                    "op": 1,
-                                    "d": *curr_sequence
-                                })
-                            };
-
-                            if let Err(e) = write.send(Message::Text(heartbeat_payload.to_string())).await {
-                                eprintln!("Failed to send heartbeat: {:?}", e);
-                            } else {
-                                println!("heartbeat sent.");
-                            }
-                            sleep(Duration::from_millis(ms)).await;
-                        }
-                        
-                        
+                    // The session is kept, it may still be resumable once the gateway is reachable.
+                    self.status.send_replace(GatewayStatus::Resuming);
+                    Some(e.to_string())
+                },
+            };
+
+            // Reconnect right away after a connection that was ready, otherwise back off.
+            if attempts == 0 {
+                continue;
+            }
+            if attempts >= MAX_CONNECT_ATTEMPTS {
+                let err = failure.unwrap_or_else(|| "the connection ended before the session was ready".to_string());
+                let _ = self.event_sender.send(Err(GatewayError::ConnectionFailed { err, attempts })).await;
+                return;
+            }
+            let delay = Duration::from_secs(2u64.pow(attempts.min(6)));
+            sleep(delay).await;
+        }
+    }
+
+    /// Sends an event that couldn't be deserialized to the stream, false if nobody is listening anymore.
+    async fn report(&self, err: serde_json::Error) -> bool {
+        let err = GatewayError::DeserializeError { err: err.to_string() };
+        self.event_sender.send(Err(err)).await.is_ok()
+    }
+
+    fn heartbeat_message(&self) -> Message {
+        let heartbeat_payload = serde_json::json!({
+            "op": 1,
+            "d": self.sequence
+        });
+        Message::Text(heartbeat_payload.to_string())
+    }
+
+    /// Resets `attempts` once the session is ready or resumed.
+    async fn run_connection(&mut self, ws: WsStream, attempts: &mut u32) -> Result<ConnectionEnd> {
+        let (mut write, mut read) = ws.split();
+        let mut heartbeat: Option<Heartbeat> = None;
+        // Each connection is its own compressed stream.
+        let mut decoder = StreamDecoder::new(self.compression)?;
+
+        loop {
+            let message = tokio::select! {
+                message = read.next() => message,
+                _ = tick(&mut heartbeat) => {
+                    let Some(heartbeat) = heartbeat.as_mut() else { continue };
+                    // The last heartbeat was never acknowledged, the connection is dead even if the socket isn't.
+                    if heartbeat.unacknowledged_since.is_some() {
+                        close_for_resume(&mut write).await;
+                        return Ok(ConnectionEnd::Resume);
                     }
-                });
+                    heartbeat.unacknowledged_since = Some(Instant::now());
+                    write.send(self.heartbeat_message()).await?;
+                    continue;
+                },
+            };
 
-                heartbeat_task
+            let recv_data = match message {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Binary(data))) => match decoder.as_mut() {
+                    Some(decoder) => match decoder.push(&data)? {
+                        Some(text) => text,
+                        None => continue,
+                    },
+                    None => continue,
+                },
+                Some(Ok(Message::Close(Some(frame)))) => {
+                    let code = GatewayCloseCode::from(u16::from(frame.code));
+                    return Ok(match code {
+                        code if code.is_resumable() => ConnectionEnd::Resume,
+                        code if code.is_reconnectable() => ConnectionEnd::Identify,
+                        code => ConnectionEnd::Closed { code, reason: frame.reason.to_string() },
+                    });
+                },
+                Some(Ok(Message::Close(None))) | None => return Ok(ConnectionEnd::Resume),
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(e.into()),
+            };
+            if recv_data.is_empty() {
+                continue;
             }
-        };
 
-        Ok(connection)
+            // A payload that can't be read is reported, but doesn't end the connection.
+            let json: Value = match serde_json::from_str(&recv_data) {
+                Ok(json) => json,
+                Err(e) => {
+                    if !self.report(e).await {
+                        return Ok(ConnectionEnd::Stop);
+                    }
+                    continue;
+                },
+            };
+            if let Some(sequence) = json.get("s").and_then(Value::as_u64) {
+                self.sequence = Some(sequence);
+            }
+
+            let recv_event = match GatewayRecieveEvent::deserialize(json) {
+                Ok(e) => e,
+                Err(e) => {
+                    if !self.report(e).await {
+                        return Ok(ConnectionEnd::Stop);
+                    }
+                    continue;
+                },
+            };
+
+            match recv_event {
+                // Heartbeats are handled automatically.
+                GatewayRecieveEvent::Hello { heartbeat_info } => {
+                    heartbeat = Some(Heartbeat::new(Duration::from_millis(heartbeat_info.heartbeat_interval)));
+
+                    let handshake = match (&self.resume, self.sequence) {
+                        (Some(resume), Some(sequence)) => GatewaySendEventRaw::resume(&self.token, &resume.session_id, sequence),
+                        _ => identify(&self.token)?,
+                    };
+                    write.send(Message::Text(serde_json::to_string(&handshake)?)).await?;
+                },
+                GatewayRecieveEvent::GeneralEvent { dispatched_event } => {
+                    match dispatched_event.as_ref() {
+                        DispatchedEvent::Ready { session_id, resume_gateway_url, .. } => {
+                            self.resume = Some(ResumeInfo {
+                                session_id: session_id.clone(),
+                                resume_gateway_url: resume_gateway_url.clone(),
+                            });
+                            self.status.send_replace(GatewayStatus::Connected);
+                            *attempts = 0;
+                        },
+                        DispatchedEvent::Resumed {} => {
+                            self.status.send_replace(GatewayStatus::Connected);
+                            *attempts = 0;
+                        },
+                        _ => {},
+                    }
+                    if self.event_sender.send(Ok(*dispatched_event)).await.is_err() {
+                        let _ = write.close().await;
+                        return Ok(ConnectionEnd::Stop);
+                    }
+                },
+                GatewayRecieveEvent::Reconnect {  } => {
+                    close_for_resume(&mut write).await;
+                    return Ok(ConnectionEnd::Resume);
+                },
+                GatewayRecieveEvent::InvalidSession { resumable } => {
+                    if resumable {
+                        close_for_resume(&mut write).await;
+                        return Ok(ConnectionEnd::Resume);
+                    }
+                    let _ = write.close().await;
+                    self.status.send_replace(GatewayStatus::Identifying);
+                    // Discord asks to wait a random 1 to 5 seconds before identifying again.
+                    let delay = Duration::from_millis(rand::thread_rng().gen_range(1000..=5000));
+                    sleep(delay).await;
+                    return Ok(ConnectionEnd::Identify);
+                },
+                GatewayRecieveEvent::HeartbeatAck {  } => {
+                    let sent_at = heartbeat.as_mut().and_then(|heartbeat| heartbeat.unacknowledged_since.take());
+                    if let Some(sent_at) = sent_at {
+                        self.latency.lock().unwrap().record(sent_at.elapsed());
+                    }
+                },
+                GatewayRecieveEvent::HeartbeatRequest {  } => {
+                    if let Some(heartbeat) = heartbeat.as_mut() {
+                        heartbeat.unacknowledged_since.get_or_insert_with(Instant::now);
+                    }
+                    write.send(self.heartbeat_message()).await?;
+                },
+                GatewayRecieveEvent::UnwantedEvent {  } => {},
+            }
+        }
     }
+}
 
-    pub fn events(self) -> impl Stream<Item = DispatchedEvent> {
-        // Create a new stream type that wraps the receiver
-        ReceiverStream::new(self.event_receiver)
+/// Closes with a code other than 1000 or 1001, which would end the session and make resuming it fail.
+async fn close_for_resume(write: &mut SplitSink<WsStream, Message>) {
+    let frame = CloseFrame {
+        code: CloseCode::from(4000),
+        reason: "".into(),
+    };
+    let _ = write.send(Message::Close(Some(frame))).await;
+}
+
+/// Whether connecting again later could work, e.g. not when the url is invalid or the gateway refuses the client.
+fn is_transient(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<WsError>() {
+        Some(WsError::Url(_)) => false,
+        Some(WsError::Http(res)) => {
+            let status = res.status();
+            !status.is_client_error() || status.as_u16() == 429
+        },
+        _ => true,
     }
-    
-    pub async fn wait_until_finish(self) {
-        self.ws_data_read_loop.await.unwrap();
+}
+
+/// Waits for the next heartbeat, forever if the interval isn't known yet.
+async fn tick(heartbeat: &mut Option<Heartbeat>) {
+    match heartbeat {
+        Some(heartbeat) => { heartbeat.interval.tick().await; },
+        None => future::pending().await,
     }
 }
 
-    // TODO! async stream api
-    /*
-    GatewayConnection should implement Stream.
-    Implement deserialize for GatewayRecieveEvent so i can just
-    call GatewayRecieveEvent::Deserialize on stream objects
-    Stream Item will be Result<DispatchedEvent>
-    */
+fn identify(token: &str) -> Result<GatewaySendEventRaw> {
+    let properties = Properties {
+        os: "Windows".to_string(),
+        browser: "Firefox".to_string(),
+        // release_channel: "stable".to_string(),
+        // client_version: "1.0.9151".to_string(),
+        // os_version: "10.0.19045".to_string(),
+        system_locale: "en-US".to_string(),
+        browser_user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) discord/1.0.9151 Chrome/120.0.6099.291 Electron/28.2.10 Safari/537.36".to_string(),
+        // browser_version: "28.2.10".to_string(),
+        // client_build_number: 304683,
+        // native_build_number: 48891,
+        // client_event_source: None,
+        // design_id: 0,
+    };
+
+    let presence = Presence {
+        status: "unknown".to_string(),
+        since: 0,
+        activities: vec![],
+        afk: false,
+        broadcast: None,
+    };
+
+    let client_state = ClientState {
+        guild_versions: std::collections::HashMap::new(),
+    };
+
+    GatewaySendEventRaw::login(
+        token.to_string(),
+        30717,
+        properties,
+        presence,
+        false,
+        client_state,
+    )
+}
+
 impl GatewaySendEventRaw {
-    pub fn login(token: String, capabilities: u32, properties: Properties, presence: Presence, compress: bool, client_state: ClientState) -> Result<Self> {
+    fn login(token: String, capabilities: u32, properties: Properties, presence: Presence, compress: bool, client_state: ClientState) -> Result<Self> {
         use serde_json::Value;
         let mut map = HashMap::<String, Value>::new();
         map.insert("token".to_string(), Value::String(token));
@@ -258,4 +565,109 @@ impl GatewaySendEventRaw {
             }
         )
     }
-}
\ No newline at end of file
+
+    fn resume(token: &str, session_id: &str, sequence: u64) -> Self {
+        Self {
+            op: 6,
+            d: serde_json::json!({
+                "token": token,
+                "session_id": session_id,
+                "seq": sequence
+            })
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Callback, ErrorResponse, Request, Response};
+    use super::*;
+
+    /// Keeps the path and query of the handshake request.
+    struct RecordUri<'a>(&'a mut String);
+
+    impl Callback for RecordUri<'_> {
+        fn on_request(self, req: &Request, res: Response) -> std::result::Result<Response, ErrorResponse> {
+            *self.0 = req.uri().to_string();
+            Ok(res)
+        }
+    }
+
+    /// Accepts a single connection, returns the path and query it was made to.
+    async fn accept_uri(listener: TcpListener) -> String {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut uri = String::new();
+        let _ws = tokio_tungstenite::accept_hdr_async(socket, RecordUri(&mut uri)).await.unwrap();
+        uri
+    }
+
+    #[tokio::test]
+    async fn connects_to_the_configured_gateway() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = ClientConfig {
+            gateway_url: format!("ws://{}/custom/gateway/", listener.local_addr().unwrap()),
+            api_version: 10,
+            gateway_compression: GatewayCompression::ZlibStream,
+            ..Default::default()
+        };
+        let server = tokio::spawn(accept_uri(listener));
+
+        let _connection = GatewayConnection::with_config("token", &config).await.unwrap();
+        assert_eq!(server.await.unwrap(), "/custom/gateway/?encoding=json&v=10&compress=zlib-stream");
+    }
+
+    /// Says hello on every connection, then closes it before the session is ready.
+    /// Stops listening after one connection more than the client should make.
+    async fn hello_then_close(listener: TcpListener, connections: Arc<AtomicU32>) {
+        while connections.load(Ordering::SeqCst) <= MAX_CONNECT_ATTEMPTS {
+            let (socket, _) = listener.accept().await.unwrap();
+            connections.fetch_add(1, Ordering::SeqCst);
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            let hello = r#"{"op":10,"d":{"heartbeat_interval":41250}}"#;
+            ws.send(Message::Text(hello.to_string())).await.unwrap();
+            let frame = CloseFrame { code: CloseCode::from(4000), reason: "".into() };
+            let _ = ws.close(Some(frame)).await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn connections_that_never_get_ready_back_off_then_give_up() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = ClientConfig {
+            gateway_url: format!("ws://{}", listener.local_addr().unwrap()),
+            ..Default::default()
+        };
+        let connections = Arc::new(AtomicU32::new(0));
+        tokio::spawn(hello_then_close(listener, connections.clone()));
+
+        let start = Instant::now();
+        let connection = GatewayConnection::with_config("token", &config).await.unwrap();
+        let status = connection.status_changes();
+        let events: Vec<_> = connection.events().collect().await;
+
+        assert!(matches!(
+            events.as_slice(),
+            [Err(GatewayError::ConnectionFailed { attempts: MAX_CONNECT_ATTEMPTS, .. })]
+        ), "{events:?}");
+        assert_eq!(connections.load(Ordering::SeqCst), MAX_CONNECT_ATTEMPTS);
+        // 2, 4, 8, 16 and 32 seconds, then 64 seconds between the rest.
+        assert!(start.elapsed() >= Duration::from_secs(2 + 4 + 8 + 16 + 32 + 64 * 4), "{:?}", start.elapsed());
+        assert_eq!(*status.borrow(), GatewayStatus::Disconnected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn heartbeats_missed_by_a_slow_consumer_dont_burst() {
+        let period = Duration::from_millis(41250);
+        let mut heartbeat = Heartbeat::new(period);
+        heartbeat.interval.tick().await;
+
+        // The connection is stuck sending an event for a few heartbeats.
+        tokio::time::advance(period * 3).await;
+        heartbeat.interval.tick().await;
+
+        let late = Instant::now();
+        heartbeat.interval.tick().await;
+        assert_eq!(late.elapsed(), period);
+    }
+}
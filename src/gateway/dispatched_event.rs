@@ -4,9 +4,7 @@ use user::UserData;
 use crate::model::*;
 
 use crate::model;
-use model::{guild::GuildMemberData, message::{GeneralMessageData, Message, MessageComponent, MessageEmbed}, voice::UserVoiceState, channel::Channel, user::{activity::*, GatewayUserData, MainUserData, RelationshipAddEvent, RelationshipAddType, RelationshipRemoveEvent, RelationshipRemoveType}};
-
-use super::{error::GatewayError, events::GatewayReceiveEventRaw};
+use model::{guild::GuildMemberData, message::{Message, MessageComponent, MessageEmbed}, voice::UserVoiceState, channel::Channel, user::{GatewayUserData, RelationshipAddEvent, RelationshipRemoveEvent}};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all(deserialize = "SCREAMING_SNAKE_CASE"))]
@@ -15,7 +13,7 @@ pub enum DispatchedEvent {
     /// Message was created
     MessageCreate {
         #[serde(flatten)]
-        message: Message,
+        message: Box<Message>,
         guild_id: Option<Snowflake>
     },
     /// Message was edited
@@ -31,7 +29,10 @@ pub enum DispatchedEvent {
     /// Contains the initial state information
     // TODO! this contains a bit too much information. Maybe handle full deserialization another day. 
     Ready {
-        user: GatewayUserData
+        user: GatewayUserData,
+        session_id: String,
+        /// Where to reconnect to when resuming the session.
+        resume_gateway_url: String
     },
     /// Defines the heartbeat interval
     Hello {
@@ -87,7 +88,11 @@ pub enum DispatchedEvent {
     },
     /// Message was pinned or unpinned
     ChannelPinsUpdate {
-        
+        guild_id: Option<Snowflake>,
+        channel_id: Snowflake,
+        /// None once the last pin is removed.
+        #[serde(default, with = "time::serde::iso8601::option")]
+        last_pin_timestamp: Option<time::OffsetDateTime>
     },
     /// Thread created, also sent when being added to a private thread
     ThreadCreate {
@@ -139,7 +144,10 @@ pub enum DispatchedEvent {
     },
     /// A guild audit log entry was created
     GuildAuditLogEntryCreate {
-        
+        /// The user and target aren't resolved for entries from the gateway.
+        #[serde(flatten)]
+        entry: Box<model::guild::audit_log::AuditLogEntry>,
+        guild_id: Snowflake
     },
     /// User was banned from a guild
     GuildBanAdd {
@@ -151,11 +159,15 @@ pub enum DispatchedEvent {
     },
     /// Guild emojis were updated
     GuildEmojisUpdate {
-        
+        guild_id: Snowflake,
+        /// All of the guild's emojis, not only the ones that changed.
+        emojis: Vec<model::message::Emoji>
     },
     /// Guild stickers were updated
     GuildStickersUpdate {
-        
+        guild_id: Snowflake,
+        /// All of the guild's stickers, not only the ones that changed.
+        stickers: Vec<model::message::Sticker>
     },
     /// Guild integration was updated
     GuildIntegrationsUpdate {
@@ -191,23 +203,32 @@ pub enum DispatchedEvent {
     },
     /// Guild scheduled event was created
     GuildScheduledEventCreate {
-        
+        #[serde(flatten)]
+        event: model::scheduled_event::ScheduledEvent
     },
     /// Guild scheduled event was updated
     GuildScheduledEventUpdate {
-        
+        #[serde(flatten)]
+        event: model::scheduled_event::ScheduledEvent
     },
     /// Guild scheduled event was deleted
     GuildScheduledEventDelete {
-        
+        #[serde(flatten)]
+        event: model::scheduled_event::ScheduledEvent
     },
     /// User subscribed to a guild scheduled event
     GuildScheduledEventUserAdd {
-        
+        #[serde(rename = "guild_scheduled_event_id")]
+        event_id: Snowflake,
+        user_id: Snowflake,
+        guild_id: Snowflake
     },
     /// User unsubscribed from a guild scheduled event
     GuildScheduledEventUserRemove {
-        
+        #[serde(rename = "guild_scheduled_event_id")]
+        event_id: Snowflake,
+        user_id: Snowflake,
+        guild_id: Snowflake
     },
     /// Guild integration was created
     IntegrationCreate {
@@ -227,11 +248,28 @@ pub enum DispatchedEvent {
     },
     /// Invite to a channel was created
     InviteCreate {
-        
+        channel_id: Snowflake,
+        code: String,
+        #[serde(with = "time::serde::iso8601")]
+        created_at: time::OffsetDateTime,
+        guild_id: Option<Snowflake>,
+        inviter: Option<UserData>,
+        /// In seconds, 0 if the invite never expires.
+        max_age: u32,
+        /// 0 for unlimited uses.
+        max_uses: u32,
+        target_type: Option<model::invite::InviteTargetType>,
+        target_user: Option<UserData>,
+        temporary: bool,
+        uses: u32,
+        #[serde(default, with = "time::serde::iso8601::option")]
+        expires_at: Option<time::OffsetDateTime>
     },
     /// Invite to a channel was deleted
     InviteDelete {
-        
+        channel_id: Snowflake,
+        guild_id: Option<Snowflake>,
+        code: String
     },
     /// Message was deleted
     MessageDelete {
@@ -298,7 +336,8 @@ pub enum DispatchedEvent {
     },
     /// Guild channel webhook was created, updated, or deleted
     WebhooksUpdate {
-        
+        guild_id: Snowflake,
+        channel_id: Snowflake
     },
     /// User voted on a poll
     MessagePollVoteAdd {
@@ -1,3 +1,4 @@
+use std::fmt::Display;
 use thiserror::Error;
 #[derive(Debug, Error)]
 pub enum GatewayError {
@@ -6,5 +7,166 @@ pub enum GatewayError {
     #[error("DeserializeError: {err}")]
     DeserializeError{ err: String },
     #[error("UnwantedEventError: {event_name}")]
-    UnwantedEventError{ event_name: String }
-}
\ No newline at end of file
+    UnwantedEventError{ event_name: String },
+    /// The gateway closed the connection for good, reconnecting wouldn't help.
+    #[error("Closed: {code} ({reason})")]
+    Closed { code: GatewayCloseCode, reason: String },
+    /// Connecting to the gateway kept failing, or failed in a way retrying wouldn't fix.
+    #[error("Connection failed after {attempts} attempts: {err}")]
+    ConnectionFailed { err: String, attempts: u32 },
+    /// The task running the connection panicked or was cancelled.
+    #[error("Connection task failed: {err}")]
+    TaskFailed { err: String },
+}
+
+// https://discord.com/developers/docs/topics/opcodes-and-status-codes#gateway-gateway-close-event-codes
+/// Why the gateway closed the connection.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GatewayCloseCode {
+    /// Something went wrong on discord's side.
+    UnknownError,
+    /// An invalid opcode or payload was sent.
+    UnknownOpcode,
+    /// An invalid payload was sent.
+    DecodeError,
+    /// A payload was sent before identifying.
+    NotAuthenticated,
+    /// The token is invalid.
+    AuthenticationFailed,
+    /// Identify was sent more than once.
+    AlreadyAuthenticated,
+    /// The sequence sent when resuming was invalid.
+    InvalidSequence,
+    /// Payloads were sent too quickly.
+    RateLimited,
+    /// The session timed out, a new one has to be identified.
+    SessionTimedOut,
+    /// An invalid shard was sent when identifying.
+    InvalidShard,
+    /// The session would handle too many guilds, sharding is needed.
+    ShardingRequired,
+    /// The api version is invalid or no longer supported.
+    InvalidApiVersion,
+    /// An invalid intent was sent when identifying.
+    InvalidIntents,
+    /// An intent the application isn't approved for was sent when identifying.
+    DisallowedIntents,
+    /// Any other code, e.g. the websocket's own codes.
+    Other(u16),
+}
+
+impl GatewayCloseCode {
+    pub fn code(&self) -> u16 {
+        match self {
+            GatewayCloseCode::UnknownError => 4000,
+            GatewayCloseCode::UnknownOpcode => 4001,
+            GatewayCloseCode::DecodeError => 4002,
+            GatewayCloseCode::NotAuthenticated => 4003,
+            GatewayCloseCode::AuthenticationFailed => 4004,
+            GatewayCloseCode::AlreadyAuthenticated => 4005,
+            GatewayCloseCode::InvalidSequence => 4007,
+            GatewayCloseCode::RateLimited => 4008,
+            GatewayCloseCode::SessionTimedOut => 4009,
+            GatewayCloseCode::InvalidShard => 4010,
+            GatewayCloseCode::ShardingRequired => 4011,
+            GatewayCloseCode::InvalidApiVersion => 4012,
+            GatewayCloseCode::InvalidIntents => 4013,
+            GatewayCloseCode::DisallowedIntents => 4014,
+            GatewayCloseCode::Other(code) => *code,
+        }
+    }
+
+    /// Whether reconnecting can work, otherwise something has to change first (e.g. the token or the intents).
+    pub fn is_reconnectable(&self) -> bool {
+        !matches!(
+            self,
+            GatewayCloseCode::AuthenticationFailed
+            | GatewayCloseCode::InvalidShard
+            | GatewayCloseCode::ShardingRequired
+            | GatewayCloseCode::InvalidApiVersion
+            | GatewayCloseCode::InvalidIntents
+            | GatewayCloseCode::DisallowedIntents
+        )
+    }
+
+    /// Whether the session can be resumed after reconnecting, otherwise a new one has to be identified.
+    pub fn is_resumable(&self) -> bool {
+        self.is_reconnectable()
+            && !matches!(self, GatewayCloseCode::InvalidSequence | GatewayCloseCode::SessionTimedOut)
+    }
+}
+
+impl From<u16> for GatewayCloseCode {
+    fn from(code: u16) -> GatewayCloseCode {
+        match code {
+            4000 => GatewayCloseCode::UnknownError,
+            4001 => GatewayCloseCode::UnknownOpcode,
+            4002 => GatewayCloseCode::DecodeError,
+            4003 => GatewayCloseCode::NotAuthenticated,
+            4004 => GatewayCloseCode::AuthenticationFailed,
+            4005 => GatewayCloseCode::AlreadyAuthenticated,
+            4007 => GatewayCloseCode::InvalidSequence,
+            4008 => GatewayCloseCode::RateLimited,
+            4009 => GatewayCloseCode::SessionTimedOut,
+            4010 => GatewayCloseCode::InvalidShard,
+            4011 => GatewayCloseCode::ShardingRequired,
+            4012 => GatewayCloseCode::InvalidApiVersion,
+            4013 => GatewayCloseCode::InvalidIntents,
+            4014 => GatewayCloseCode::DisallowedIntents,
+            code => GatewayCloseCode::Other(code),
+        }
+    }
+}
+
+impl Display for GatewayCloseCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GatewayCloseCode::Other(code) => write!(f, "{code}"),
+            known => write!(f, "{} {known:?}", known.code()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_codes_are_classified() {
+        use GatewayCloseCode::*;
+        // (code, variant, reconnectable, resumable)
+        let table = [
+            (4000, UnknownError, true, true),
+            (4001, UnknownOpcode, true, true),
+            (4002, DecodeError, true, true),
+            (4003, NotAuthenticated, true, true),
+            (4004, AuthenticationFailed, false, false),
+            (4005, AlreadyAuthenticated, true, true),
+            (4006, Other(4006), true, true),
+            (4007, InvalidSequence, true, false),
+            (4008, RateLimited, true, true),
+            (4009, SessionTimedOut, true, false),
+            (4010, InvalidShard, false, false),
+            (4011, ShardingRequired, false, false),
+            (4012, InvalidApiVersion, false, false),
+            (4013, InvalidIntents, false, false),
+            (4014, DisallowedIntents, false, false),
+            (1000, Other(1000), true, true),
+            (1006, Other(1006), true, true),
+        ];
+
+        for (code, variant, reconnectable, resumable) in table {
+            let parsed = GatewayCloseCode::from(code);
+            assert_eq!(parsed, variant, "{code}");
+            assert_eq!(parsed.code(), code);
+            assert_eq!(parsed.is_reconnectable(), reconnectable, "{code} reconnectable");
+            assert_eq!(parsed.is_resumable(), resumable, "{code} resumable");
+        }
+    }
+
+    #[test]
+    fn close_codes_display_their_number() {
+        assert_eq!(GatewayCloseCode::AuthenticationFailed.to_string(), "4004 AuthenticationFailed");
+        assert_eq!(GatewayCloseCode::Other(4999).to_string(), "4999");
+    }
+}
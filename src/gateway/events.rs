@@ -1,16 +1,13 @@
 #![allow(dead_code)]
-use arboard::Clipboard;
 use serde::{Deserialize, Serialize};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use anyhow::Result;
 use serde_json::Value;
-use tokio::io::BufWriter;
-use super::dispatched_event::DispatchedEvent;
-use super::error::GatewayError;
 use serde::de::Error;
-use std::fs::File;
-use std::io::Write;
+use serde::de::value::MapDeserializer;
+use std::sync::OnceLock;
+use super::dispatched_event::DispatchedEvent;
 
 #[derive(Serialize, PartialEq, Debug)]
 pub struct GatewaySendEventRaw
@@ -26,6 +23,7 @@ pub struct GatewayReceiveEventRaw
     pub op: u32,
     // worry about sequence field later
     // pub s: u32,
+    #[serde(default)]
     pub d: serde_json::Value,
 }
 
@@ -108,7 +106,7 @@ pub struct HeartbeatInfo {
 #[derive(Debug)]
 pub enum GatewayRecieveEvent {
     GeneralEvent {
-        dispatched_event: DispatchedEvent, 
+        dispatched_event: Box<DispatchedEvent>, 
     },
     UnwantedEvent {
     },
@@ -118,56 +116,60 @@ pub enum GatewayRecieveEvent {
     },
     HeartbeatAck {
     },
+    /// The gateway wants a heartbeat right away.
+    HeartbeatRequest {
+    },
+    /// The gateway wants the client to reconnect and resume.
+    Reconnect {
+    },
+    /// The session couldn't be identified or resumed, or was invalidated.
+    InvalidSession {
+        /// Whether the session can still be resumed, otherwise a new one has to be identified.
+        resumable: bool,
+    },
 }
  
 impl<'de> serde::Deserialize<'de> for GatewayRecieveEvent {
     fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         let value = Value::deserialize(d)?;
-        // TODO! comment out someday
-        let recieved_json = serde_json::to_string_pretty(&value).unwrap();
-        let opcode = value.get("op").unwrap();
-        let opcode = opcode.as_u64().unwrap();
-        let opcode = FromPrimitive::from_u8(opcode as u8).unwrap();
+        let opcode = value.get("op")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| D::Error::custom("missing or invalid op"))?;
+        // Opcodes added to the gateway after this crate are skipped.
+        let Some(opcode) = u8::try_from(opcode).ok().and_then(GatewayOpCode::from_u8) else {
+            return Ok(Self::UnwantedEvent {  });
+        };
         // Dispatched event handled separately bc ownership stuff
         if opcode == GatewayOpCode::Dispatch {
-            if value.get("d").unwrap().is_array() {
+            if value.get("d").is_some_and(Value::is_array) {
                 return Ok(
                     Self::UnwantedEvent {  }
                 )
             }
-            // println!("got this json from message: {}", value.get("d").unwrap());
-            let data = DispatchedEvent::deserialize(value);
-            if let Ok(e) = data {
-                return Ok(
+            // Events this crate doesn't know about yet are skipped, errors in the ones it knows aren't.
+            if value.get("t").and_then(Value::as_str).is_some_and(|name| !is_known_event(name)) {
+                return Ok(Self::UnwantedEvent {  });
+            }
+            return match DispatchedEvent::deserialize(value) {
+                Ok(e) => Ok(
                     Self::GeneralEvent {
-                        dispatched_event: e,
+                        dispatched_event: Box::new(e),
                     }
-                );
-            } else {
-                // Save the json that caused the fail on error
-                eprintln!("Error: {data:?}");
-                let mut json_save_file = File::create("last_fail.json").expect("Failed to open file you're cooked");
-                json_save_file.write(recieved_json.as_bytes()).unwrap();
-                eprintln!("Recieved json has been saved...");
-                panic!();
-            }
+                ),
+                Err(e) => Err(D::Error::custom(e)),
+            };
         }
-        let raw = GatewayReceiveEventRaw::deserialize(value).unwrap();
+        let raw = GatewayReceiveEventRaw::deserialize(value).map_err(D::Error::custom)?;
         let gateway_recv_event = match opcode {
-            GatewayOpCode::Heartbeat => {
-                todo!()
-                // let data = HeartbeatInfo::deserialize(raw.d).unwrap();
-                // GatewayRecieveEvent::Hello(data)
+            GatewayOpCode::Heartbeat => Self::HeartbeatRequest {  },
+            GatewayOpCode::Reconnect => Self::Reconnect {  },
+            GatewayOpCode::InvalidSession => {
+                Self::InvalidSession {
+                    resumable: raw.d.as_bool().unwrap_or(false),
+                }
             },
-            GatewayOpCode::Identify => todo!(),
-            GatewayOpCode::PresenceUpdate => todo!(),
-            GatewayOpCode::VoiceStateUpdate => todo!(),
-            GatewayOpCode::Resume => todo!(),
-            GatewayOpCode::Reconnect => todo!(),
-            GatewayOpCode::RequestGuildMembers => todo!(),
-            GatewayOpCode::InvalidSession => todo!(),
             GatewayOpCode::Hello => {
-                let heartbeat_info = HeartbeatInfo::deserialize(raw.d).unwrap();
+                let heartbeat_info = HeartbeatInfo::deserialize(raw.d).map_err(D::Error::custom)?;
                 Self::Hello { 
                     heartbeat_info,
                 }
@@ -175,10 +177,201 @@ impl<'de> serde::Deserialize<'de> for GatewayRecieveEvent {
             GatewayOpCode::HeartbeatAck => {
                 Self::HeartbeatAck {  }
             },
-            _ => {
-                panic!("Invalid opcode not handled...");
-            }
+            // Only ever sent by the client.
+            GatewayOpCode::Dispatch
+            | GatewayOpCode::Identify
+            | GatewayOpCode::PresenceUpdate
+            | GatewayOpCode::VoiceStateUpdate
+            | GatewayOpCode::Resume
+            | GatewayOpCode::RequestGuildMembers => Self::UnwantedEvent {  },
         };
         Ok(gateway_recv_event)
     }
-}
\ No newline at end of file
+}
+
+/// Whether `DispatchedEvent` has a variant for the event name.
+fn is_known_event(name: &str) -> bool {
+    static NAMES: OnceLock<&'static [&'static str]> = OnceLock::new();
+    let names = NAMES.get_or_init(|| {
+        // Serde hands the names of every variant to the error for a tag that matches none of them.
+        let unknown_tag = MapDeserializer::<_, KnownVariants>::new([("t", "")].into_iter());
+        match DispatchedEvent::deserialize(unknown_tag) {
+            Err(KnownVariants(names)) => names,
+            Ok(_) => &[],
+        }
+    });
+    names.contains(&name)
+}
+
+/// Keeps the variants serde expected when it finds an unknown one, and nothing of any other error.
+#[derive(Debug)]
+struct KnownVariants(&'static [&'static str]);
+
+impl std::fmt::Display for KnownVariants {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected one of {:?}", self.0)
+    }
+}
+
+impl std::error::Error for KnownVariants {}
+
+impl serde::de::Error for KnownVariants {
+    fn custom<T: std::fmt::Display>(_msg: T) -> Self {
+        KnownVariants(&[])
+    }
+
+    fn unknown_variant(_variant: &str, expected: &'static [&'static str]) -> Self {
+        KnownVariants(expected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::model::guild::audit_log::{AuditLogChange, EventType};
+    use crate::model::scheduled_event::{ScheduledEventEntityType, ScheduledEventStatus};
+    use crate::model::Snowflake;
+    use super::*;
+
+    #[test]
+    fn hello_and_invalid_session_are_parsed() {
+        let hello = GatewayRecieveEvent::deserialize(json!({"op": 10, "d": {"heartbeat_interval": 41250}})).unwrap();
+        assert!(matches!(hello, GatewayRecieveEvent::Hello { heartbeat_info } if heartbeat_info.heartbeat_interval == 41250));
+
+        let invalid = GatewayRecieveEvent::deserialize(json!({"op": 9, "d": true})).unwrap();
+        assert!(matches!(invalid, GatewayRecieveEvent::InvalidSession { resumable: true }));
+    }
+
+    #[test]
+    fn heartbeat_ack_without_data_is_parsed() {
+        let ack = GatewayRecieveEvent::deserialize(json!({"op": 11})).unwrap();
+        assert!(matches!(ack, GatewayRecieveEvent::HeartbeatAck {}));
+    }
+
+    #[test]
+    fn unknown_opcodes_and_events_are_skipped() {
+        for payload in [
+            json!({"op": 42, "d": null}),
+            json!({"op": 3, "d": {}}),
+            json!({"op": 0, "s": 5, "t": "SOME_FUTURE_EVENT", "d": {"id": "1"}}),
+        ] {
+            let event = GatewayRecieveEvent::deserialize(payload).unwrap();
+            assert!(matches!(event, GatewayRecieveEvent::UnwantedEvent {}), "{event:?}");
+        }
+    }
+
+    #[test]
+    fn malformed_payloads_are_errors() {
+        assert!(GatewayRecieveEvent::deserialize(json!({"d": null})).is_err());
+        assert!(GatewayRecieveEvent::deserialize(json!({"op": 10, "d": {}})).is_err());
+        let pins_update = json!({"op": 0, "s": 5, "t": "CHANNEL_PINS_UPDATE", "d": {"guild_id": 1}});
+        assert!(GatewayRecieveEvent::deserialize(pins_update).is_err());
+        assert!(GatewayRecieveEvent::deserialize(json!({"op": 0, "s": 5, "d": {}})).is_err());
+    }
+
+    #[test]
+    fn event_names_are_known_from_dispatched_event() {
+        for name in ["READY", "MESSAGE_CREATE", "GUILD_AUDIT_LOG_ENTRY_CREATE", "GUILD_SCHEDULED_EVENT_USER_REMOVE"] {
+            assert!(is_known_event(name), "{name}");
+        }
+        for name in ["SOME_FUTURE_EVENT", "MessageCreate", ""] {
+            assert!(!is_known_event(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn known_events_with_bad_values_are_errors() {
+        let scheduled_event = json!({"op": 0, "s": 5, "t": "GUILD_SCHEDULED_EVENT_CREATE", "d": {
+            "id": "1198664934917546046",
+            "guild_id": "1197917493435846697",
+            "name": "Movie night",
+            "scheduled_start_time": "2024-02-01T20:00:00+00:00",
+            "privacy_level": 2,
+            "status": 9,
+            "entity_type": 3
+        }});
+        let err = GatewayRecieveEvent::deserialize(scheduled_event).unwrap_err();
+        assert!(err.to_string().contains('9'), "{err}");
+    }
+
+    fn dispatched(payload: Value) -> DispatchedEvent {
+        match GatewayRecieveEvent::deserialize(payload).unwrap() {
+            GatewayRecieveEvent::GeneralEvent { dispatched_event } => *dispatched_event,
+            event => panic!("not a dispatched event: {event:?}"),
+        }
+    }
+
+    #[test]
+    fn audit_log_entries_are_dispatched() {
+        let event = dispatched(json!({"op": 0, "s": 5, "t": "GUILD_AUDIT_LOG_ENTRY_CREATE", "d": {
+            "id": "1198664934917546046",
+            "guild_id": "1197917493435846697",
+            "action_type": 24,
+            "user_id": "80351110224678912",
+            "target_id": "41771983423143937",
+            "changes": [{"key": "nick", "old_value": "Nelly", "new_value": "Nelly!"}],
+            "reason": "typo"
+        }}));
+
+        let DispatchedEvent::GuildAuditLogEntryCreate { entry, guild_id } = event else {
+            panic!("{event:?}");
+        };
+        assert_eq!(guild_id, Snowflake::new("1197917493435846697"));
+        assert_eq!(entry.id, Snowflake::new("1198664934917546046"));
+        assert_eq!(entry.action_type, EventType::MemberUpdate);
+        assert_eq!(entry.target_id, Some(Snowflake::new("41771983423143937")));
+        assert!(matches!(
+            entry.changes.as_slice(),
+            [AuditLogChange::Nick { old: Some(old), new: Some(new) }] if old == "Nelly" && new == "Nelly!"
+        ));
+        assert_eq!(entry.reason.as_deref(), Some("typo"));
+        assert!(entry.user.is_none() && entry.target.is_none());
+    }
+
+    #[test]
+    fn scheduled_events_are_dispatched() {
+        let scheduled_event = json!({
+            "id": "1198664934917546046",
+            "guild_id": "1197917493435846697",
+            "channel_id": null,
+            "creator_id": "80351110224678912",
+            "name": "Movie night",
+            "description": null,
+            "scheduled_start_time": "2024-02-01T20:00:00+00:00",
+            "scheduled_end_time": "2024-02-01T23:00:00+00:00",
+            "privacy_level": 2,
+            "status": 1,
+            "entity_type": 3,
+            "entity_id": null,
+            "entity_metadata": {"location": "The park"},
+            "image": null,
+            "recurrence_rule": null
+        });
+        for t in ["GUILD_SCHEDULED_EVENT_CREATE", "GUILD_SCHEDULED_EVENT_UPDATE", "GUILD_SCHEDULED_EVENT_DELETE"] {
+            let event = dispatched(json!({"op": 0, "s": 5, "t": t, "d": scheduled_event}));
+            let (DispatchedEvent::GuildScheduledEventCreate { event }
+            | DispatchedEvent::GuildScheduledEventUpdate { event }
+            | DispatchedEvent::GuildScheduledEventDelete { event }) = event else {
+                panic!("{event:?}");
+            };
+            assert_eq!(event.id, Snowflake::new("1198664934917546046"));
+            assert_eq!(event.status, ScheduledEventStatus::Scheduled);
+            assert_eq!(event.entity_type, ScheduledEventEntityType::External);
+            assert_eq!(event.location(), Some("The park"));
+        }
+
+        let subscription = json!({
+            "guild_scheduled_event_id": "1198664934917546046",
+            "user_id": "80351110224678912",
+            "guild_id": "1197917493435846697"
+        });
+        let event = dispatched(json!({"op": 0, "s": 6, "t": "GUILD_SCHEDULED_EVENT_USER_ADD", "d": subscription}));
+        assert!(matches!(
+            event,
+            DispatchedEvent::GuildScheduledEventUserAdd { event_id, user_id, .. }
+                if event_id == Snowflake::new("1198664934917546046") && user_id == Snowflake::new("80351110224678912")
+        ));
+        let event = dispatched(json!({"op": 0, "s": 7, "t": "GUILD_SCHEDULED_EVENT_USER_REMOVE", "d": subscription}));
+        assert!(matches!(event, DispatchedEvent::GuildScheduledEventUserRemove { .. }));
+    }
+}
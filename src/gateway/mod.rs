@@ -1,3 +1,4 @@
+pub mod compression;
 pub mod connection;
 pub mod dispatched_event;
 pub mod events;
@@ -0,0 +1,714 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use num::FromPrimitive;
+use num_derive::FromPrimitive;
+use reqwest::{Method, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::Value;
+use super::ratelimit::Route;
+
+/// How much of a response body is kept in a `RequestContext`.
+const MAX_BODY_LEN: usize = 1024;
+
+/// Response headers worth keeping around when debugging a failed request.
+const HEADERS_OF_INTEREST: &[&str] = &[
+    "x-ratelimit-bucket",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset-after",
+    "x-ratelimit-scope",
+    "x-ratelimit-global",
+    "retry-after",
+    "content-type",
+];
+
+/// Describes the request a `QueryError` came from.
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub method: Method,
+    /// The route with its ids replaced by placeholders, e.g. `/channels/:major/messages/:id`.
+    pub route: String,
+    /// None if no response was received.
+    pub status: Option<StatusCode>,
+    /// The cloudflare ray id of the response, which identifies the request to discord.
+    pub request_id: Option<String>,
+    pub headers: Vec<(String, String)>,
+    /// The start of the response body.
+    pub body: Option<String>,
+}
+
+impl RequestContext {
+    pub(crate) fn new(route: &Route) -> RequestContext {
+        RequestContext {
+            method: route.method().clone(),
+            route: route.template().to_string(),
+            status: None,
+            request_id: None,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub(crate) fn from_response(route: &Route, res: &Response) -> RequestContext {
+        let header = |name: &str| res.headers().get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        RequestContext {
+            status: Some(res.status()),
+            request_id: header("cf-ray").or_else(|| header("x-request-id")),
+            headers: HEADERS_OF_INTEREST.iter()
+                .filter_map(|name| Some((name.to_string(), header(name)?)))
+                .collect(),
+            ..RequestContext::new(route)
+        }
+    }
+
+    pub(crate) fn set_body(&mut self, body: &str) {
+        if body.is_empty() {
+            return;
+        }
+
+        let mut end = body.len().min(MAX_BODY_LEN);
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.body = Some(body[..end].to_string());
+    }
+}
+
+impl Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.route)?;
+        if let Some(status) = self.status {
+            write!(f, " -> {status}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, ", request {request_id}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error response from discord's api.
+#[derive(Debug, Clone)]
+pub struct DiscordApiError {
+    pub code: DiscordErrorCode,
+    /// The code exactly as discord sent it, for codes that aren't in `DiscordErrorCode` yet.
+    /// None if the body had no code, e.g. an error page from a proxy.
+    pub raw_code: Option<i64>,
+    pub message: String,
+    pub status: StatusCode,
+    /// Which fields of the request body were invalid, and why.
+    /// Paths are dot separated, e.g. `embeds.0.title`.
+    pub errors: BTreeMap<String, Vec<FieldError>>,
+    /// How many seconds to wait before trying again, if rate limited.
+    pub retry_after: Option<f64>,
+    /// Whether the rate limit applies to every request rather than a single bucket.
+    pub global: bool,
+}
+
+/// A single reason a field in the request body was rejected.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Deserialize, Default)]
+struct RawApiError {
+    code: Option<i64>,
+    message: Option<String>,
+    errors: Option<Value>,
+    retry_after: Option<f64>,
+    #[serde(default)]
+    global: bool,
+}
+
+impl DiscordApiError {
+    /// Parses an error body, falling back to the status alone if it isn't a discord error object.
+    pub fn new(status: StatusCode, json: &Value) -> DiscordApiError {
+        let raw = RawApiError::deserialize(json).unwrap_or_default();
+        let raw_code = raw.code;
+
+        let mut errors = BTreeMap::new();
+        if let Some(tree) = &raw.errors {
+            flatten_field_errors(tree, "", &mut errors);
+        }
+
+        DiscordApiError {
+            code: raw_code.and_then(DiscordErrorCode::from_i64).unwrap_or(DiscordErrorCode::Unknown),
+            raw_code,
+            message: raw.message
+                .unwrap_or_else(|| status.canonical_reason().unwrap_or_default().to_string()),
+            status,
+            errors,
+            retry_after: raw.retry_after,
+            global: raw.global,
+        }
+    }
+
+    pub fn is_rate_limit(&self) -> bool {
+        self.status == StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+// Nested errors look like {"embeds": {"0": {"title": {"_errors": [{"code": ..., "message": ...}]}}}}
+fn flatten_field_errors(tree: &Value, path: &str, out: &mut BTreeMap<String, Vec<FieldError>>) {
+    let Value::Object(map) = tree else {
+        return;
+    };
+
+    for (key, value) in map {
+        if key == "_errors" {
+            let errors = Vec::<FieldError>::deserialize(value).unwrap_or_default();
+            out.entry(path.to_string()).or_default().extend(errors);
+        } else if path.is_empty() {
+            flatten_field_errors(value, key, out);
+        } else {
+            flatten_field_errors(value, &format!("{path}.{key}"), out);
+        }
+    }
+}
+
+impl Display for DiscordApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.raw_code {
+            Some(code) => write!(f, "Error {code} ({}): {}", self.status, self.message)?,
+            None => write!(f, "Error ({}): {}", self.status, self.message)?,
+        }
+        if let Some(retry_after) = self.retry_after {
+            write!(f, ", retry after {retry_after} seconds")?;
+        }
+        for (path, errors) in &self.errors {
+            for error in errors {
+                write!(f, "\n  {path}: {} ({})", error.message, error.code)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DiscordApiError {}
+
+/// JSON error codes returned by discord.
+/// https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes
+#[derive(FromPrimitive, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i64)]
+pub enum DiscordErrorCode {
+    /// General error (such as a malformed request body, amongst other things)
+    GeneralError = 0,
+    /// Unknown account
+    UnknownAccount = 10001,
+    /// Unknown application
+    UnknownApplication = 10002,
+    /// Unknown channel
+    UnknownChannel = 10003,
+    /// Unknown guild
+    UnknownGuild = 10004,
+    /// Unknown integration
+    UnknownIntegration = 10005,
+    /// Unknown invite
+    UnknownInvite = 10006,
+    /// Unknown member
+    UnknownMember = 10007,
+    /// Unknown message
+    UnknownMessage = 10008,
+    /// Unknown permission overwrite
+    UnknownPermissionOverwrite = 10009,
+    /// Unknown provider
+    UnknownProvider = 10010,
+    /// Unknown role
+    UnknownRole = 10011,
+    /// Unknown token
+    UnknownToken = 10012,
+    /// Unknown user
+    UnknownUser = 10013,
+    /// Unknown emoji
+    UnknownEmoji = 10014,
+    /// Unknown webhook
+    UnknownWebhook = 10015,
+    /// Unknown webhook service
+    UnknownWebhookService = 10016,
+    /// Unknown session
+    UnknownSession = 10020,
+    /// Unknown asset
+    UnknownAsset = 10021,
+    /// Unknown ban
+    UnknownBan = 10026,
+    /// Unknown SKU
+    UnknownSku = 10027,
+    /// Unknown Store Listing
+    UnknownStoreListing = 10028,
+    /// Unknown entitlement
+    UnknownEntitlement = 10029,
+    /// Unknown build
+    UnknownBuild = 10030,
+    /// Unknown lobby
+    UnknownLobby = 10031,
+    /// Unknown branch
+    UnknownBranch = 10032,
+    /// Unknown store directory layout
+    UnknownStoreDirectoryLayout = 10033,
+    /// Unknown redistributable
+    UnknownRedistributable = 10036,
+    /// Unknown gift code
+    UnknownGiftCode = 10038,
+    /// Unknown stream
+    UnknownStream = 10049,
+    /// Unknown premium server subscribe cooldown
+    UnknownPremiumServerSubscribeCooldown = 10050,
+    /// Unknown guild template
+    UnknownGuildTemplate = 10057,
+    /// Unknown discoverable server category
+    UnknownDiscoverableServerCategory = 10059,
+    /// Unknown sticker
+    UnknownSticker = 10060,
+    /// Unknown sticker pack
+    UnknownStickerPack = 10061,
+    /// Unknown interaction
+    UnknownInteraction = 10062,
+    /// Unknown application command
+    UnknownApplicationCommand = 10063,
+    /// Unknown voice state
+    UnknownVoiceState = 10065,
+    /// Unknown application command permissions
+    UnknownApplicationCommandPermissions = 10066,
+    /// Unknown Stage Instance
+    UnknownStageInstance = 10067,
+    /// Unknown Guild Member Verification Form
+    UnknownGuildMemberVerificationForm = 10068,
+    /// Unknown Guild Welcome Screen
+    UnknownGuildWelcomeScreen = 10069,
+    /// Unknown Guild Scheduled Event
+    UnknownGuildScheduledEvent = 10070,
+    /// Unknown Guild Scheduled Event User
+    UnknownGuildScheduledEventUser = 10071,
+    /// Unknown Tag
+    UnknownTag = 10087,
+    /// Unknown sound
+    UnknownSound = 10097,
+    /// Bots cannot use this endpoint
+    BotsCannotUseEndpoint = 20001,
+    /// Only bots can use this endpoint
+    OnlyBotsCanUseEndpoint = 20002,
+    /// Explicit content cannot be sent to the desired recipient(s)
+    ExplicitContentCannotBeSent = 20009,
+    /// You are not authorized to perform this action on this application
+    NotAuthorizedForApplication = 20012,
+    /// This action cannot be performed due to slowmode rate limit
+    SlowmodeRateLimit = 20016,
+    /// Only the owner of this account can perform this action
+    OnlyAccountOwner = 20018,
+    /// This message cannot be edited due to announcement rate limits
+    AnnouncementEditRateLimit = 20022,
+    /// Under minimum age
+    UnderMinimumAge = 20024,
+    /// The channel you are writing has hit the write rate limit
+    WriteChannelRateLimitReached = 20028,
+    /// The write action you are performing on the server has hit the write rate limit
+    WriteServerRateLimitReached = 20029,
+    /// Your Stage topic, server name, server description, or channel names contain words that are not allowed
+    DisallowedWords = 20031,
+    /// Guild premium subscription level too low
+    GuildPremiumLevelTooLow = 20035,
+    /// Maximum number of guilds reached (100)
+    MaxGuildsReached = 30001,
+    /// Maximum number of friends reached (1000)
+    MaxFriendsReached = 30002,
+    /// Maximum number of pins reached for the channel (50)
+    MaxPinsReached = 30003,
+    /// Maximum number of recipients reached (10)
+    MaxRecipientsReached = 30004,
+    /// Maximum number of guild roles reached (250)
+    MaxGuildRolesReached = 30005,
+    /// Maximum number of webhooks reached (15)
+    MaxWebhooksReached = 30007,
+    /// Maximum number of emojis reached
+    MaxEmojisReached = 30008,
+    /// Maximum number of reactions reached (20)
+    MaxReactionsReached = 30010,
+    /// Maximum number of group DMs reached (10)
+    MaxGroupDmsReached = 30011,
+    /// Maximum number of guild channels reached (500)
+    MaxGuildChannelsReached = 30013,
+    /// Maximum number of attachments in a message reached (10)
+    MaxAttachmentsReached = 30015,
+    /// Maximum number of invites reached (1000)
+    MaxInvitesReached = 30016,
+    /// Maximum number of animated emojis reached
+    MaxAnimatedEmojisReached = 30018,
+    /// Maximum number of server members reached
+    MaxServerMembersReached = 30019,
+    /// Maximum number of server categories has been reached (5)
+    MaxServerCategoriesReached = 30030,
+    /// Guild already has a template
+    GuildAlreadyHasTemplate = 30031,
+    /// Maximum number of application commands reached
+    MaxApplicationCommandsReached = 30032,
+    /// Maximum number of thread participants has been reached (1000)
+    MaxThreadParticipantsReached = 30033,
+    /// Maximum number of daily application command creates has been reached (200)
+    MaxDailyApplicationCommandCreatesReached = 30034,
+    /// Maximum number of bans for non-guild members have been exceeded
+    MaxNonMemberBansReached = 30035,
+    /// Maximum number of bans fetches has been reached
+    MaxBanFetchesReached = 30037,
+    /// Maximum number of uncompleted guild scheduled events reached (100)
+    MaxUncompletedScheduledEventsReached = 30038,
+    /// Maximum number of stickers reached
+    MaxStickersReached = 30039,
+    /// Maximum number of prune requests has been reached. Try again later
+    MaxPruneRequestsReached = 30040,
+    /// Maximum number of guild widget settings updates has been reached. Try again later
+    MaxWidgetSettingsUpdatesReached = 30042,
+    /// Maximum number of soundboard sounds reached
+    MaxSoundboardSoundsReached = 30045,
+    /// Maximum number of edits to messages older than 1 hour reached. Try again later
+    MaxOldMessageEditsReached = 30046,
+    /// Maximum number of pinned threads in a forum channel has been reached
+    MaxPinnedForumThreadsReached = 30047,
+    /// Maximum number of tags in a forum channel has been reached
+    MaxForumTagsReached = 30048,
+    /// Bitrate is too high for channel of this type
+    BitrateTooHigh = 30052,
+    /// Maximum number of premium emojis reached (25)
+    MaxPremiumEmojisReached = 30056,
+    /// Maximum number of webhooks per guild reached (1000)
+    MaxGuildWebhooksReached = 30058,
+    /// Maximum number of channel permission overwrites reached (1000)
+    MaxPermissionOverwritesReached = 30060,
+    /// The channels for this guild are too large
+    GuildChannelsTooLarge = 30061,
+    /// Unauthorized. Provide a valid token and try again
+    Unauthorized = 40001,
+    /// You need to verify your account in order to perform this action
+    AccountVerificationRequired = 40002,
+    /// You are opening direct messages too fast
+    OpeningDmsTooFast = 40003,
+    /// Send messages has been temporarily disabled
+    SendMessagesDisabled = 40004,
+    /// Request entity too large. Try sending something smaller in size
+    RequestEntityTooLarge = 40005,
+    /// This feature has been temporarily disabled server-side
+    FeatureDisabled = 40006,
+    /// The user is banned from this guild
+    UserBannedFromGuild = 40007,
+    /// Connection has been revoked
+    ConnectionRevoked = 40012,
+    /// Only consumable SKUs can be consumed
+    OnlyConsumableSkus = 40018,
+    /// You can only delete sandbox entitlements
+    OnlySandboxEntitlements = 40019,
+    /// Target user is not connected to voice
+    TargetNotInVoice = 40032,
+    /// This message has already been crossposted
+    AlreadyCrossposted = 40033,
+    /// An application command with that name already exists
+    ApplicationCommandNameExists = 40041,
+    /// Application interaction failed to send
+    InteractionFailedToSend = 40043,
+    /// Cannot send a message in a forum channel
+    CannotSendInForumChannel = 40058,
+    /// Interaction has already been acknowledged
+    InteractionAlreadyAcknowledged = 40060,
+    /// Tag names must be unique
+    TagNamesMustBeUnique = 40061,
+    /// Service resource is being rate limited
+    ServiceResourceRateLimited = 40062,
+    /// There are no tags available that can be set by non-moderators
+    NoTagsAvailableForNonModerators = 40066,
+    /// A tag is required to create a forum post in this channel
+    TagRequiredForForumPost = 40067,
+    /// An entitlement has already been granted for this resource
+    EntitlementAlreadyGranted = 40074,
+    /// This interaction has hit the maximum number of follow up messages
+    MaxFollowUpMessagesReached = 40094,
+    /// Cloudflare is blocking your request. This can often be resolved by setting a proper User Agent
+    CloudflareBlocked = 40333,
+    /// Missing access
+    MissingAccess = 50001,
+    /// Invalid account type
+    InvalidAccountType = 50002,
+    /// Cannot execute action on a DM channel
+    CannotExecuteOnDmChannel = 50003,
+    /// Guild widget disabled
+    GuildWidgetDisabled = 50004,
+    /// Cannot edit a message authored by another user
+    CannotEditOtherUsersMessage = 50005,
+    /// Cannot send an empty message
+    CannotSendEmptyMessage = 50006,
+    /// Cannot send messages to this user
+    CannotSendToUser = 50007,
+    /// Cannot send messages in a non-text channel
+    CannotSendInNonTextChannel = 50008,
+    /// Channel verification level is too high for you to gain access
+    ChannelVerificationLevelTooHigh = 50009,
+    /// OAuth2 application does not have a bot
+    OAuth2ApplicationHasNoBot = 50010,
+    /// OAuth2 application limit reached
+    OAuth2ApplicationLimitReached = 50011,
+    /// Invalid OAuth2 state
+    InvalidOAuth2State = 50012,
+    /// You lack permissions to perform that action
+    MissingPermissions = 50013,
+    /// Invalid authentication token provided
+    InvalidAuthenticationToken = 50014,
+    /// Note was too long
+    NoteTooLong = 50015,
+    /// Provided too few or too many messages to delete. Must provide at least 2 and fewer than 100 messages to delete
+    InvalidBulkDeleteCount = 50016,
+    /// Invalid MFA Level
+    InvalidMfaLevel = 50017,
+    /// A message can only be pinned to the channel it was sent in
+    PinInWrongChannel = 50019,
+    /// Invite code was either invalid or taken
+    InvalidInviteCode = 50020,
+    /// Cannot execute action on a system message
+    CannotExecuteOnSystemMessage = 50021,
+    /// Cannot execute action on this channel type
+    CannotExecuteOnChannelType = 50024,
+    /// Invalid OAuth2 access token provided
+    InvalidOAuth2AccessToken = 50025,
+    /// Missing required OAuth2 scope
+    MissingOAuth2Scope = 50026,
+    /// Invalid webhook token provided
+    InvalidWebhookToken = 50027,
+    /// Invalid role
+    InvalidRole = 50028,
+    /// Invalid Recipient(s)
+    InvalidRecipients = 50033,
+    /// A message provided was too old to bulk delete
+    MessageTooOldToBulkDelete = 50034,
+    /// Invalid form body, or invalid Content-Type provided
+    InvalidFormBody = 50035,
+    /// An invite was accepted to a guild the application's bot is not in
+    InviteAcceptedToGuildWithoutBot = 50036,
+    /// Invalid Activity Action
+    InvalidActivityAction = 50039,
+    /// Invalid API version provided
+    InvalidApiVersion = 50041,
+    /// File uploaded exceeds the maximum size
+    FileTooLarge = 50045,
+    /// Invalid file uploaded
+    InvalidFileUploaded = 50046,
+    /// Cannot self-redeem this gift
+    CannotSelfRedeemGift = 50054,
+    /// Invalid Guild
+    InvalidGuild = 50055,
+    /// Invalid SKU
+    InvalidSku = 50057,
+    /// Invalid request origin
+    InvalidRequestOrigin = 50067,
+    /// Invalid message type
+    InvalidMessageType = 50068,
+    /// Payment source required to redeem gift
+    PaymentSourceRequired = 50070,
+    /// Cannot modify a system webhook
+    CannotModifySystemWebhook = 50073,
+    /// Cannot delete a channel required for Community guilds
+    CannotDeleteCommunityChannel = 50074,
+    /// Cannot edit stickers within a message
+    CannotEditMessageStickers = 50080,
+    /// Invalid sticker sent
+    InvalidSticker = 50081,
+    /// Tried to perform an operation on an archived thread
+    ThreadArchived = 50083,
+    /// Invalid thread notification settings
+    InvalidThreadNotificationSettings = 50084,
+    /// before value is earlier than the thread creation date
+    BeforeEarlierThanThreadCreation = 50085,
+    /// Community server channels must be text channels
+    CommunityChannelsMustBeText = 50086,
+    /// The entity type of the event is different from the entity you are trying to start the event for
+    EventEntityTypeMismatch = 50091,
+    /// This server is not available in your location
+    ServerUnavailableInLocation = 50095,
+    /// This server needs monetization enabled in order to perform this action
+    MonetizationRequired = 50097,
+    /// This server needs more boosts to perform this action
+    MoreBoostsRequired = 50101,
+    /// The request body contains invalid JSON
+    InvalidJson = 50109,
+    /// The provided file is invalid
+    InvalidFile = 50110,
+    /// The provided file type is invalid
+    InvalidFileType = 50123,
+    /// The provided file duration exceeds maximum of 5.2 seconds
+    FileDurationTooLong = 50124,
+    /// Owner cannot be pending member
+    OwnerCannotBePending = 50131,
+    /// Ownership cannot be transferred to a bot user
+    CannotTransferOwnershipToBot = 50132,
+    /// Failed to resize asset below the maximum size: 262144
+    AssetResizeFailed = 50138,
+    /// Cannot mix subscription and non subscription roles for an emoji
+    CannotMixSubscriptionRoles = 50144,
+    /// Cannot convert between premium emoji and normal emoji
+    CannotConvertPremiumEmoji = 50145,
+    /// Uploaded file not found
+    UploadedFileNotFound = 50146,
+    /// The specified emoji is invalid
+    InvalidEmoji = 50151,
+    /// Voice messages do not support additional content
+    VoiceMessageWithContent = 50159,
+    /// Voice messages must have a single audio attachment
+    VoiceMessageAttachmentCount = 50160,
+    /// Voice messages must have supporting metadata
+    VoiceMessageMissingMetadata = 50161,
+    /// Voice messages cannot be edited
+    CannotEditVoiceMessage = 50162,
+    /// Cannot delete guild subscription integration
+    CannotDeleteSubscriptionIntegration = 50163,
+    /// You cannot send voice messages in this channel
+    CannotSendVoiceMessages = 50173,
+    /// The user account must first be verified
+    AccountVerificationRequiredFirst = 50178,
+    /// The provided file does not have a valid duration
+    InvalidFileDuration = 50192,
+    /// You do not have permission to send this sticker
+    MissingStickerPermission = 50600,
+    /// Two factor is required for this operation
+    TwoFactorRequired = 60003,
+    /// No users with DiscordTag exist
+    NoUsersWithDiscordTag = 80004,
+    /// Reaction was blocked
+    ReactionBlocked = 90001,
+    /// User cannot use burst reactions
+    CannotUseBurstReactions = 90002,
+    /// Application not yet available. Try again later
+    ApplicationNotAvailable = 110001,
+    /// API resource is currently overloaded. Try again a little later
+    ResourceOverloaded = 130000,
+    /// The Stage is already open
+    StageAlreadyOpen = 150006,
+    /// Cannot reply without permission to read message history
+    CannotReplyWithoutReadHistory = 160002,
+    /// A thread has already been created for this message
+    ThreadAlreadyCreated = 160004,
+    /// Thread is locked
+    ThreadLocked = 160005,
+    /// Maximum number of active threads reached
+    MaxActiveThreadsReached = 160006,
+    /// Maximum number of active announcement threads reached
+    MaxActiveAnnouncementThreadsReached = 160007,
+    /// Invalid JSON for uploaded Lottie file
+    InvalidLottieJson = 170001,
+    /// Uploaded Lotties cannot contain rasterized images such as PNG or JPEG
+    LottieContainsRasterImages = 170002,
+    /// Sticker maximum framerate exceeded
+    StickerFramerateExceeded = 170003,
+    /// Sticker frame count exceeds maximum of 1000 frames
+    StickerFrameCountExceeded = 170004,
+    /// Lottie animation maximum dimensions exceeded
+    LottieDimensionsExceeded = 170005,
+    /// Sticker frame rate is either too small or too large
+    InvalidStickerFrameRate = 170006,
+    /// Sticker animation duration exceeds maximum of 5 seconds
+    StickerDurationExceeded = 170007,
+    /// Cannot update a finished event
+    CannotUpdateFinishedEvent = 180000,
+    /// Failed to create stage needed for stage event
+    StageEventCreationFailed = 180002,
+    /// Message was blocked by automatic moderation
+    BlockedByAutoModeration = 200000,
+    /// Title was blocked by automatic moderation
+    TitleBlockedByAutoModeration = 200001,
+    /// Webhooks posted to forum channels must have a thread_name or thread_id
+    ForumWebhookRequiresThread = 220001,
+    /// Webhooks posted to forum channels cannot have both a thread_name and thread_id
+    ForumWebhookThreadNameAndId = 220002,
+    /// Webhooks can only create threads in forum channels
+    WebhookThreadsOnlyInForums = 220003,
+    /// Webhook services cannot be used in forum channels
+    WebhookServicesInForum = 220004,
+    /// Message blocked by harmful links filter
+    BlockedByHarmfulLinksFilter = 240000,
+    /// Cannot enable onboarding, requirements are not met
+    OnboardingRequirementsNotMet = 350000,
+    /// Cannot update onboarding while below requirements
+    OnboardingBelowRequirements = 350001,
+    /// Failed to ban users
+    FailedToBanUsers = 500000,
+    /// Poll voting blocked
+    PollVotingBlocked = 520000,
+    /// Poll expired
+    PollExpired = 520001,
+    /// Invalid channel type for poll creation
+    InvalidPollChannelType = 520002,
+    /// Cannot edit a poll message
+    CannotEditPollMessage = 520003,
+    /// Cannot use an emoji included with the poll
+    CannotUsePollEmoji = 520004,
+    /// Cannot expire a non-poll message
+    CannotExpireNonPoll = 520006,
+    /// A code not known to this crate, see `DiscordApiError::raw_code`.
+    /// Also used when the response had no code at all.
+    Unknown = -1
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    #[test]
+    fn nested_field_errors_are_flattened() {
+        let body = json!({
+            "code": 50035,
+            "message": "Invalid Form Body",
+            "errors": {
+                "content": {"_errors": [{"code": "BASE_TYPE_MAX_LENGTH", "message": "Must be 2000 or fewer in length."}]},
+                "embeds": {"0": {"title": {"_errors": [
+                    {"code": "BASE_TYPE_REQUIRED", "message": "This field is required"},
+                    {"code": "BASE_TYPE_MAX_LENGTH", "message": "Must be 256 or fewer in length."}
+                ]}}}
+            }
+        });
+        let error = DiscordApiError::new(StatusCode::BAD_REQUEST, &body);
+
+        assert_eq!(error.code, DiscordErrorCode::InvalidFormBody);
+        assert_eq!(error.raw_code, Some(50035));
+        assert_eq!(error.errors.keys().collect::<Vec<_>>(), ["content", "embeds.0.title"]);
+        assert_eq!(error.errors["embeds.0.title"].len(), 2);
+        assert_eq!(error.errors["content"][0].code, "BASE_TYPE_MAX_LENGTH");
+    }
+
+    #[test]
+    fn unknown_codes_are_kept() {
+        let body = json!({"code": 987654, "message": "Something new"});
+        let error = DiscordApiError::new(StatusCode::FORBIDDEN, &body);
+
+        assert_eq!(error.code, DiscordErrorCode::Unknown);
+        assert_eq!(error.raw_code, Some(987654));
+        assert_eq!(error.message, "Something new");
+    }
+
+    #[test]
+    fn general_error_needs_an_actual_code() {
+        let error = DiscordApiError::new(StatusCode::BAD_REQUEST, &json!({"code": 0, "message": "General error"}));
+        assert_eq!(error.code, DiscordErrorCode::GeneralError);
+    }
+
+    #[test]
+    fn non_json_bodies_fall_back_to_the_status() {
+        // What validate_response passes along for e.g. an html error page.
+        let error = DiscordApiError::new(StatusCode::BAD_GATEWAY, &Value::Null);
+
+        assert_eq!(error.code, DiscordErrorCode::Unknown);
+        assert_eq!(error.raw_code, None);
+        assert_eq!(error.message, "Bad Gateway");
+        assert!(error.errors.is_empty());
+        assert_eq!(error.to_string(), "Error (502 Bad Gateway): Bad Gateway");
+    }
+
+    #[test]
+    fn rate_limit_bodies_are_parsed() {
+        let body = json!({"message": "You are being rate limited.", "retry_after": 1.5, "global": true, "code": 0});
+        let error = DiscordApiError::new(StatusCode::TOO_MANY_REQUESTS, &body);
+
+        assert!(error.is_rate_limit());
+        assert_eq!(error.retry_after, Some(1.5));
+        assert!(error.global);
+    }
+}
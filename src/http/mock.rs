@@ -0,0 +1,107 @@
+//! A stand-in for discord's api in tests, answering requests with queued json bodies.
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::client::{DiscordClient, DiscordClientBuilder};
+use crate::http::retry::RetryPolicy;
+
+/// The current user, which the client fetches when it's built.
+pub(crate) const ME: &str = r#"{"id":"80351110224678912","username":"Nelly","avatar":null,"discriminator":"1337","public_flags":0,"premium_type":0,"flags":0,"banner":null,"accent_color":null,"global_name":null,"avatar_decoration_data":null,"banner_color":null,"mfa_enabled":false,"locale":"en-US","email":"nelly@discord.com","verified":true,"phone":null,"nsfw_allowed":false,"linked_users":[],"bio":"","authenticator_types":[]}"#;
+
+pub(crate) struct MockApi {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<String>>>,
+    server: JoinHandle<()>,
+}
+
+impl MockApi {
+    /// Answers each request with the next body, and with a 404 once they run out.
+    pub(crate) async fn start<I: IntoIterator<Item = String>>(bodies: I) -> MockApi {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut bodies: VecDeque<String> = bodies.into_iter().collect();
+
+        let received = requests.clone();
+        let server = tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let request = read_request(&mut socket).await;
+                received.lock().unwrap().push(request);
+                let response = match bodies.pop_front() {
+                    Some(body) => format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_string(),
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        MockApi { addr, requests, server }
+    }
+
+    pub(crate) fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// A client of this api, which answers its first request with `ME`.
+    pub(crate) async fn client(&self) -> DiscordClient {
+        DiscordClientBuilder::new("token")
+            .set_api_base(&format!("http://{}/api", self.addr))
+            .set_api_version(10)
+            .set_retry_policy(RetryPolicy::never())
+            .allow_plain_http()
+            .build().await
+            .unwrap()
+    }
+
+    /// The method and target of every request received so far, e.g. `GET /api/v10/users/@me`.
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockApi {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Reads the request's head and body, returns its method and target.
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0; 4096];
+    let head_len = loop {
+        if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+        let len = socket.read(&mut buf).await.unwrap_or(0);
+        if len == 0 {
+            break request.len();
+        }
+        request.extend_from_slice(&buf[..len]);
+    };
+
+    let head = String::from_utf8_lossy(&request[..head_len]).to_string();
+    let content_length = head.lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while request.len() < head_len + content_length {
+        let len = socket.read(&mut buf).await.unwrap_or(0);
+        if len == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..len]);
+    }
+
+    let request_line = head.lines().next().unwrap_or_default();
+    request_line.trim_end_matches(" HTTP/1.1").to_string()
+}
@@ -0,0 +1,284 @@
+pub mod error;
+#[cfg(test)]
+pub(crate) mod mock;
+pub mod ratelimit;
+pub mod retry;
+
+use std::sync::Arc;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::header;
+use reqwest::multipart::Form;
+use reqwest::{header::HeaderMap, Client, ClientBuilder, Method, Request, RequestBuilder, StatusCode};
+use reqwest::Response;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use tokio::time::{sleep, Duration};
+
+use crate::builders::attachment::{multipart_form, Attachment};
+pub use error::{DiscordApiError, DiscordErrorCode, FieldError, RequestContext};
+use ratelimit::{RateLimiter, Route};
+use retry::{RetryAttempt, RetryPolicy};
+
+pub fn build_request_client(auth: &str, ua: &str, https_only: bool) -> Result<Client, reqwest::Error> {
+    let mut headers = HeaderMap::new();
+
+    let mut auth_value =
+        header::HeaderValue::from_str(auth).expect("Failed to convert auth token to header.");
+    auth_value.set_sensitive(true);
+    headers.insert("Authorization", auth_value);
+
+    client_builder(ua, https_only)
+        .default_headers(headers)
+        .build()
+}
+
+/// A client without an Authorization header, for routes that authenticate through the url (e.g. webhook tokens).
+pub fn build_unauthenticated_request_client(ua: &str, https_only: bool) -> Result<Client, reqwest::Error> {
+    client_builder(ua, https_only).build()
+}
+
+fn client_builder(ua: &str, https_only: bool) -> ClientBuilder {
+    let cb = ClientBuilder::new();
+    cb.https_only(https_only)
+        .cookie_store(true)
+        // actual stuff for discord
+        .user_agent(ua)
+}
+
+/// The client every request to discord goes through.
+/// Cloning it is cheap, and clones share the same rate limits.
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    inner: Client,
+    api_url: String,
+    ratelimiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+}
+
+impl HttpClient {
+    /// `api_url` is the versioned root every route is relative to,
+    /// e.g. `https://discord.com/api/v9`.
+    pub fn new(client: Client, api_url: String, retry_policy: RetryPolicy) -> HttpClient {
+        HttpClient {
+            inner: client,
+            api_url,
+            ratelimiter: Arc::new(RateLimiter::new()),
+            retry_policy,
+        }
+    }
+
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    pub fn request(&self, method: Method, route: &str) -> RequestBuilder {
+        self.inner.request(method, format!("{}{route}", self.api_url))
+    }
+
+    /// Sends a request to the route and deserializes the response,
+    /// retrying according to the client's retry policy.
+    /// `build` can add a body or headers to the request.
+    /// Requests with a streamed body can't be copied, so they are only ever sent once.
+    pub async fn execute<T, F>(&self, method: Method, route: &str, build: F) -> Result<T, QueryError>
+    where
+        T: DeserializeOwned,
+        F: FnOnce(RequestBuilder) -> RequestBuilder,
+    {
+        let route = Route::new(&method, route);
+        let mut request = build(self.request(method.clone(), route.path())).build()
+            .map_err(|e| QueryError::reqwest(e, RequestContext::new(&route)))?;
+        let mut attempts: Vec<RetryAttempt> = Vec::new();
+
+        loop {
+            let next_request = request.try_clone();
+            let (status, retry_after, result) = match self.send(&route, request).await {
+                Ok(res) => (Some(res.status()), retry_after(&res), validate_response(res, &route).await),
+                Err(e) => (None, None, Err(e)),
+            };
+            let err = match result {
+                Ok(value) => return Ok(value),
+                Err(e) => e,
+            };
+
+            let attempt = attempts.len() as u32 + 1;
+            let delay = self.retry_policy.retry_delay(attempt, &method, status, retry_after);
+            match (next_request, delay) {
+                (Some(next_request), Some(delay)) => {
+                    attempts.push(RetryAttempt { status, error: err.to_string(), delay });
+                    sleep(delay).await;
+                    request = next_request;
+                },
+                _ if attempts.is_empty() => return Err(err),
+                _ => {
+                    attempts.push(RetryAttempt { status, error: err.to_string(), delay: Duration::ZERO });
+                    return Err(QueryError::RetriesExhausted { attempts, error: Box::new(err) });
+                }
+            }
+        }
+    }
+
+    /// Sends the request once its rate limit bucket allows it.
+    async fn send(&self, route: &Route, request: Request) -> Result<Response, QueryError> {
+        self.ratelimiter.run(route, self.inner.execute(request)).await
+            .map_err(|e| QueryError::reqwest(e, RequestContext::new(route)))
+    }
+}
+
+fn retry_after(res: &Response) -> Option<Duration> {
+    res.headers().get(header::RETRY_AFTER)?
+        .to_str().ok()?
+        .parse::<f64>().ok()
+        .map(|secs| Duration::from_secs_f64(secs.max(0.0)))
+}
+
+#[derive(Error, Debug)]
+pub enum QueryError {
+    #[error("Placeholder error")]
+    Placeholder,
+
+    #[error("Reqwest error: {err} ({context})")]
+    ReqwestError { err: reqwest::Error, context: Box<RequestContext> },
+
+    #[error("Serde error: {err:?} ({context})")]
+    SerdeError { err: serde_json::Error, context: Box<RequestContext> },
+
+    #[error("Discord error: {error} ({context})")]
+    DiscordError { error: DiscordApiError, context: Box<RequestContext> },
+
+    #[error("Server error: {status} ({context})")]
+    ServerError { status: StatusCode, context: Box<RequestContext> },
+
+    #[error("IO error: {err}")]
+    IoError { err: std::io::Error },
+
+    #[error("Request failed after {} attempts: {error}", attempts.len())]
+    RetriesExhausted { attempts: Vec<RetryAttempt>, error: Box<QueryError> },
+
+    /// The request was rejected before being sent, discord would refuse it anyway.
+    #[error("Invalid request: {error}")]
+    InvalidRequest { error: String },
+
+    #[error("Unhandled Error: {error}")]
+    Other { error: String },
+}
+
+impl QueryError {
+    /// Drops the url from the error, it can contain secrets such as webhook tokens.
+    pub(crate) fn reqwest(err: reqwest::Error, context: RequestContext) -> QueryError {
+        QueryError::ReqwestError { err: err.without_url(), context: Box::new(context) }
+    }
+
+    /// The request that caused this error, if it came from one.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            QueryError::ReqwestError { context, .. }
+            | QueryError::SerdeError { context, .. }
+            | QueryError::DiscordError { context, .. }
+            | QueryError::ServerError { context, .. } => Some(context),
+            QueryError::RetriesExhausted { error, .. } => error.context(),
+            QueryError::Placeholder | QueryError::IoError { .. }
+            | QueryError::InvalidRequest { .. } | QueryError::Other { .. } => None,
+        }
+    }
+}
+
+/// Sets the reason shown in the guild's audit log for the action the request performs.
+pub fn audit_log_reason(req: RequestBuilder, reason: Option<&str>) -> RequestBuilder {
+    match reason {
+        Some(reason) => req.header("X-Audit-Log-Reason", utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string()),
+        None => req,
+    }
+}
+
+pub async fn get_struct<T>(client: HttpClient, route: &str, method: reqwest::Method) -> Result<T, QueryError>
+where
+    T: DeserializeOwned,
+{
+    client.execute(method, route, |req| req).await
+}
+
+pub async fn get_struct_body<T: DeserializeOwned, S: Serialize>(client: HttpClient, route: &str, body: &S, method: reqwest::Method) -> Result<T, QueryError>
+{
+    client.execute(method, route, |req| req.json(body)).await
+}
+
+/// Sends the payload as `payload_json` along with the attachments, as `multipart/form-data`.
+pub async fn get_struct_multipart<T: DeserializeOwned, S: Serialize>(client: HttpClient, route: &str, payload: &S, attachments: Vec<Attachment>, method: reqwest::Method) -> Result<T, QueryError>
+{
+    get_struct_multipart_with_reason(client, route, payload, attachments, method, None).await
+}
+
+pub async fn get_struct_multipart_with_reason<T: DeserializeOwned, S: Serialize>(client: HttpClient, route: &str, payload: &S, attachments: Vec<Attachment>, method: reqwest::Method, reason: Option<&str>) -> Result<T, QueryError>
+{
+    let form = multipart_form(payload, attachments).await?;
+    client.execute(method, route, |req| audit_log_reason(req.multipart(form), reason)).await
+}
+
+/// Sends an already built `multipart/form-data` body.
+pub async fn get_struct_form_with_reason<T: DeserializeOwned>(client: HttpClient, route: &str, form: Form, method: reqwest::Method, reason: Option<&str>) -> Result<T, QueryError>
+{
+    client.execute(method, route, |req| audit_log_reason(req.multipart(form), reason)).await
+}
+
+pub async fn get_json(client: HttpClient, route: &str, method: reqwest::Method) -> Result<serde_json::Value, QueryError> {
+    get_struct(client, route, method).await
+}
+
+pub async fn get_json_body<T: Serialize>(client: HttpClient, route: &str, body: &T, method: reqwest::Method) -> Result<serde_json::Value, QueryError> {
+    get_struct_body(client, route, body, method).await
+}
+
+pub async fn send(client: HttpClient, route: &str, method: reqwest::Method) -> Result<(), QueryError> {
+    get_struct::<IgnoredAny>(client, route, method).await.map(|_| ())
+}
+
+pub async fn send_body<T: Serialize>(client: HttpClient, route: &str, body: &T, method: reqwest::Method) -> Result<(), QueryError> {
+    get_struct_body::<IgnoredAny, T>(client, route, body, method).await.map(|_| ())
+}
+
+pub async fn get_struct_with_reason<T: DeserializeOwned>(client: HttpClient, route: &str, method: reqwest::Method, reason: Option<&str>) -> Result<T, QueryError> {
+    client.execute(method, route, |req| audit_log_reason(req, reason)).await
+}
+
+pub async fn get_struct_body_with_reason<T: DeserializeOwned, S: Serialize>(client: HttpClient, route: &str, body: &S, method: reqwest::Method, reason: Option<&str>) -> Result<T, QueryError> {
+    client.execute(method, route, |req| audit_log_reason(req.json(body), reason)).await
+}
+
+pub async fn send_with_reason(client: HttpClient, route: &str, method: reqwest::Method, reason: Option<&str>) -> Result<(), QueryError> {
+    get_struct_with_reason::<IgnoredAny>(client, route, method, reason).await.map(|_| ())
+}
+
+pub async fn send_body_with_reason<T: Serialize>(client: HttpClient, route: &str, body: &T, method: reqwest::Method, reason: Option<&str>) -> Result<(), QueryError> {
+    get_struct_body_with_reason::<IgnoredAny, T>(client, route, body, method, reason).await.map(|_| ())
+}
+
+pub async fn validate_response<T: DeserializeOwned>(res: Response, route: &Route) -> Result<T, QueryError> {
+    let status = res.status();
+    let mut context = RequestContext::from_response(route, &res);
+
+    let mut response_text: String = match res.text().await {
+        Ok(text) => text,
+        Err(e) => return Err(QueryError::reqwest(e, context)),
+    };
+    context.set_body(&response_text);
+    let context = Box::new(context);
+
+    if status.is_server_error() {
+        return Err(QueryError::ServerError { status, context });
+    }
+
+    if response_text.is_empty() {
+        response_text = "{}".to_string();
+    }
+
+    if !status.is_success() {
+        // Error bodies aren't always json, the status alone is enough then.
+        let json = serde_json::from_str::<Value>(&response_text).unwrap_or(Value::Null);
+        return Err(QueryError::DiscordError { error: DiscordApiError::new(status, &json), context });
+    }
+
+    serde_json::from_str::<T>(&response_text)
+        .map_err(|e| QueryError::SerdeError { err: e, context })
+}
@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, PoisonError};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Response, StatusCode};
+use tokio::sync::Notify;
+use tokio::time::{sleep_until, Duration, Instant};
+
+// https://discord.com/developers/docs/topics/rate-limits
+const BUCKET_HEADER: &str = "x-ratelimit-bucket";
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RESET_AFTER_HEADER: &str = "x-ratelimit-reset-after";
+const GLOBAL_HEADER: &str = "x-ratelimit-global";
+const RETRY_AFTER_HEADER: &str = "retry-after";
+
+/// Path segments whose following id is a major parameter.
+/// Routes with different major parameters never share a bucket.
+const MAJOR_PARAMETERS: &[&str] = &["channels", "guilds", "webhooks"];
+
+/// How often buckets that have reset are dropped.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A request's route with its minor parameters stripped out.
+/// Used to find the rate limit bucket a request belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Route {
+    method: Method,
+    path: String,
+    template: String,
+    major: Option<String>,
+}
+
+impl Route {
+    /// `path` is relative to the api url, and may include a query.
+    pub fn new(method: &Method, path: &str) -> Route {
+        let mut template = String::new();
+        let mut major: Option<String> = None;
+        let mut prev: Option<&str> = None;
+        let mut in_webhook = false;
+
+        let segments = path.split('?').next().unwrap_or_default()
+            .split('/')
+            .filter(|segment| !segment.is_empty());
+        for segment in segments {
+            template.push('/');
+            match prev {
+                Some(p) if major.is_none() && MAJOR_PARAMETERS.contains(&p) && is_id(segment) => {
+                    in_webhook = p == "webhooks";
+                    major = Some(segment.to_string());
+                    template.push_str(":major");
+                },
+                // webhook tokens are secrets, they never end up in a bucket key
+                Some(_) if in_webhook => {
+                    in_webhook = false;
+                    template.push_str(":token");
+                },
+                Some("reactions") => template.push_str(":emoji"),
+                Some("invites") => template.push_str(":code"),
+                _ if is_id(segment) => template.push_str(":id"),
+                _ => template.push_str(segment),
+            }
+            prev = Some(segment);
+        }
+
+        Route {
+            method: method.clone(),
+            path: path.to_string(),
+            template,
+            major,
+        }
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The route's path, with ids replaced by placeholders.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    pub fn major(&self) -> Option<&str> {
+        self.major.as_deref()
+    }
+
+    fn key(&self) -> String {
+        format!("{} {}", self.method, self.template)
+    }
+
+    fn bucket_key(&self, hash: &str) -> String {
+        format!("{hash}:{}", self.major.as_deref().unwrap_or_default())
+    }
+}
+
+fn is_id(segment: &str) -> bool {
+    !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[derive(Debug, Default)]
+struct BucketState {
+    remaining: Option<u64>,
+    reset_at: Option<Instant>,
+    /// A request is in flight to find out the bucket's limits.
+    probing: bool,
+}
+
+enum Reservation {
+    /// The request can be sent.
+    Ready,
+    /// The request can be sent, and will find out the bucket's limits.
+    Probe,
+    /// The bucket is out of requests until then.
+    Until(Instant),
+    /// Another request is finding out the bucket's limits.
+    Pending,
+}
+
+impl BucketState {
+    fn reserve(&mut self, now: Instant) -> Reservation {
+        if self.reset_at.is_some_and(|reset_at| reset_at <= now) {
+            self.remaining = None;
+            self.reset_at = None;
+        }
+
+        match (self.remaining, self.reset_at) {
+            (Some(0), Some(reset_at)) => Reservation::Until(reset_at),
+            (Some(remaining), _) => {
+                self.remaining = Some(remaining.saturating_sub(1));
+                Reservation::Ready
+            },
+            (None, _) if self.probing => Reservation::Pending,
+            (None, _) => {
+                self.probing = true;
+                Reservation::Probe
+            },
+        }
+    }
+
+    fn is_stale(&self, now: Instant) -> bool {
+        !self.probing && self.reset_at.is_none_or(|reset_at| reset_at <= now)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Bucket {
+    state: Mutex<BucketState>,
+    /// Wakes requests waiting on a probing request.
+    probed: Notify,
+}
+
+/// Held by the request probing a bucket. Ends the probe once dropped, even if the request
+/// was dropped before its response arrived, so the requests waiting on it aren't stuck.
+struct Probe<'a>(&'a Bucket);
+
+impl Drop for Probe<'_> {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap_or_else(PoisonError::into_inner).probing = false;
+        self.0.probed.notify_waiters();
+    }
+}
+
+/// Keeps track of discord's rate limit buckets.
+/// Requests only wait when their bucket has run out of requests, until it resets.
+/// While a bucket's limits are unknown, its requests are sent one at a time.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Route keys mapped to the bucket hash discord reported for them.
+    hashes: Mutex<HashMap<String, String>>,
+    buckets: Mutex<HashMap<String, Arc<Bucket>>>,
+    global_reset: Mutex<Option<Instant>>,
+    last_prune: Mutex<Instant>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter {
+            hashes: Mutex::default(),
+            buckets: Mutex::default(),
+            global_reset: Mutex::default(),
+            last_prune: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+impl RateLimiter {
+    pub fn new() -> RateLimiter {
+        RateLimiter::default()
+    }
+
+    /// Waits until the route's bucket allows another request, then sends it.
+    pub(crate) async fn run<F>(&self, route: &Route, request: F) -> reqwest::Result<Response>
+    where
+        F: Future<Output = reqwest::Result<Response>>,
+    {
+        let (bucket, probing) = loop {
+            self.wait_global().await;
+
+            let bucket = self.bucket(route);
+            let waker = bucket.clone();
+            let probed = waker.probed.notified();
+            tokio::pin!(probed);
+            // Registers for the wakeup before checking, so one can't be missed in between.
+            probed.as_mut().enable();
+
+            let reservation = bucket.state.lock().unwrap().reserve(Instant::now());
+            match reservation {
+                Reservation::Ready => break (bucket, false),
+                Reservation::Probe => break (bucket, true),
+                Reservation::Until(reset_at) => sleep_until(reset_at).await,
+                Reservation::Pending => probed.await,
+            }
+        };
+        let _probe = probing.then(|| Probe(&bucket));
+
+        let res = request.await;
+        let status = res.as_ref().ok().map(Response::status);
+        let headers = res.as_ref().ok().map(Response::headers);
+        self.update(route, &bucket, status, headers);
+
+        res
+    }
+
+    async fn wait_global(&self) {
+        let reset_at = *self.global_reset.lock().unwrap();
+        if let Some(reset_at) = reset_at {
+            sleep_until(reset_at).await;
+        }
+    }
+
+    fn bucket(&self, route: &Route) -> Arc<Bucket> {
+        self.prune();
+
+        let key = self.bucket_key(route);
+        self.buckets.lock().unwrap()
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+
+    fn bucket_key(&self, route: &Route) -> String {
+        match self.hashes.lock().unwrap().get(&route.key()) {
+            Some(hash) => route.bucket_key(hash),
+            None => format!("{}:{}", route.key(), route.major().unwrap_or_default()),
+        }
+    }
+
+    /// Drops the buckets that have reset, every id a request was ever sent to would have one otherwise.
+    fn prune(&self) {
+        let now = Instant::now();
+        {
+            let mut last_prune = self.last_prune.lock().unwrap();
+            if now.duration_since(*last_prune) < PRUNE_INTERVAL {
+                return;
+            }
+            *last_prune = now;
+        }
+
+        self.buckets.lock().unwrap()
+            .retain(|_, bucket| !bucket.state.lock().unwrap().is_stale(now));
+    }
+
+    /// Applies a response's rate limit headers to the bucket it was sent from.
+    /// `status` and `headers` are None if no response was received.
+    fn update(&self, route: &Route, bucket: &Arc<Bucket>, status: Option<StatusCode>, headers: Option<&HeaderMap>) {
+        let now = Instant::now();
+        let hash = headers.and_then(|headers| parse_header::<String>(headers, BUCKET_HEADER));
+
+        {
+            let mut state = bucket.state.lock().unwrap();
+            if let Some(headers) = headers {
+                if let Some(remaining) = parse_header::<u64>(headers, REMAINING_HEADER) {
+                    // Responses to requests sent earlier may still arrive, don't hand out their slots again.
+                    state.remaining = Some(state.remaining.map_or(remaining, |r| r.min(remaining)));
+                }
+                if let Some(reset_after) = parse_header::<f64>(headers, RESET_AFTER_HEADER) {
+                    state.reset_at = Some(now + secs(reset_after));
+                }
+            }
+
+            if let (Some(StatusCode::TOO_MANY_REQUESTS), Some(headers)) = (status, headers) {
+                let retry_after = parse_header::<f64>(headers, RETRY_AFTER_HEADER).unwrap_or(1.0);
+                let reset_at = now + secs(retry_after);
+
+                if parse_header::<bool>(headers, GLOBAL_HEADER).unwrap_or(false) {
+                    *self.global_reset.lock().unwrap() = Some(reset_at);
+                } else {
+                    state.remaining = Some(0);
+                    state.reset_at = Some(reset_at);
+                }
+            }
+        }
+
+        if let Some(hash) = hash {
+            // Share the current bucket with every other route discord puts under this hash.
+            self.buckets.lock().unwrap()
+                .entry(route.bucket_key(&hash))
+                .or_insert_with(|| bucket.clone());
+            self.hashes.lock().unwrap().insert(route.key(), hash);
+        }
+    }
+}
+
+fn secs(secs: f64) -> Duration {
+    Duration::from_secs_f64(secs.max(0.0))
+}
+
+fn parse_header<T: FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?
+        .to_str().ok()?
+        .parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+    use tokio::time::{sleep, timeout};
+    use tokio_tungstenite::tungstenite::http;
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn is_limited(bucket: &Bucket) -> bool {
+        matches!(bucket.state.lock().unwrap().reserve(Instant::now()), Reservation::Until(_))
+    }
+
+    #[test]
+    fn channel_routes_are_keyed_by_channel() {
+        let route = Route::new(&Method::GET, "/channels/123/messages/456?limit=5");
+        assert_eq!(route.template(), "/channels/:major/messages/:id");
+        assert_eq!(route.major(), Some("123"));
+
+        let other = Route::new(&Method::GET, "/channels/123/messages/789");
+        assert_eq!(route.key(), other.key());
+
+        let reaction = Route::new(&Method::PUT, "/channels/123/messages/456/reactions/%F0%9F%91%8D/@me");
+        assert_eq!(reaction.template(), "/channels/:major/messages/:id/reactions/:emoji/@me");
+    }
+
+    #[test]
+    fn webhook_tokens_are_not_part_of_the_bucket() {
+        let route = Route::new(&Method::POST, "/webhooks/123/secret-token?wait=true");
+        assert_eq!(route.template(), "/webhooks/:major/:token");
+        assert_eq!(route.major(), Some("123"));
+
+        let limiter = RateLimiter::new();
+        let bucket = limiter.bucket(&route);
+        limiter.update(&route, &bucket, Some(StatusCode::OK), Some(&headers(&[(BUCKET_HEADER, "abcd")])));
+
+        let keys = limiter.buckets.lock().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert!(!keys.is_empty());
+        assert!(keys.iter().all(|key| !key.contains("secret-token")), "{keys:?}");
+
+        let other_token = Route::new(&Method::POST, "/webhooks/123/other-token");
+        assert!(Arc::ptr_eq(&bucket, &limiter.bucket(&other_token)));
+    }
+
+    #[test]
+    fn requests_only_wait_once_the_bucket_is_empty() {
+        let route = Route::new(&Method::GET, "/channels/123/messages");
+        let limiter = RateLimiter::new();
+        let bucket = limiter.bucket(&route);
+
+        // The first request finds out the limits, others wait for it.
+        let mut state = bucket.state.lock().unwrap();
+        assert!(matches!(state.reserve(Instant::now()), Reservation::Probe));
+        assert!(matches!(state.reserve(Instant::now()), Reservation::Pending));
+        drop(state);
+
+        limiter.update(&route, &bucket, Some(StatusCode::OK), Some(&headers(&[
+            (REMAINING_HEADER, "2"),
+            (RESET_AFTER_HEADER, "10"),
+        ])));
+        let mut state = bucket.state.lock().unwrap();
+        assert!(matches!(state.reserve(Instant::now()), Reservation::Ready));
+        assert!(matches!(state.reserve(Instant::now()), Reservation::Ready));
+        assert!(matches!(state.reserve(Instant::now()), Reservation::Until(_)));
+    }
+
+    #[test]
+    fn route_rate_limits_only_apply_to_the_bucket() {
+        let route = Route::new(&Method::POST, "/channels/123/messages");
+        let limiter = RateLimiter::new();
+        let bucket = limiter.bucket(&route);
+
+        limiter.update(&route, &bucket, Some(StatusCode::TOO_MANY_REQUESTS), Some(&headers(&[
+            (RETRY_AFTER_HEADER, "3.5"),
+            (GLOBAL_HEADER, "false"),
+        ])));
+
+        assert!(limiter.global_reset.lock().unwrap().is_none());
+        assert!(is_limited(&bucket));
+        let other_channel = Route::new(&Method::POST, "/channels/456/messages");
+        assert!(!is_limited(&limiter.bucket(&other_channel)));
+    }
+
+    #[test]
+    fn global_rate_limits_apply_to_every_bucket() {
+        let route = Route::new(&Method::POST, "/channels/123/messages");
+        let limiter = RateLimiter::new();
+        let bucket = limiter.bucket(&route);
+        bucket.state.lock().unwrap().reserve(Instant::now());
+
+        limiter.update(&route, &bucket, Some(StatusCode::TOO_MANY_REQUESTS), Some(&headers(&[
+            (RETRY_AFTER_HEADER, "3.5"),
+            (GLOBAL_HEADER, "true"),
+        ])));
+
+        let reset_at = limiter.global_reset.lock().unwrap().expect("global reset wasn't set");
+        assert!(reset_at > Instant::now() + Duration::from_secs(3));
+        assert!(!is_limited(&bucket));
+    }
+
+    #[test]
+    fn reset_buckets_are_pruned() {
+        let limiter = RateLimiter::new();
+        let stale = Route::new(&Method::GET, "/channels/1/messages");
+        let limited = Route::new(&Method::GET, "/channels/2/messages");
+        limiter.bucket(&stale);
+        let bucket = limiter.bucket(&limited);
+        limiter.update(&limited, &bucket, Some(StatusCode::OK), Some(&headers(&[
+            (REMAINING_HEADER, "0"),
+            (RESET_AFTER_HEADER, "600"),
+        ])));
+
+        *limiter.last_prune.lock().unwrap() -= PRUNE_INTERVAL;
+        limiter.prune();
+
+        let keys = limiter.buckets.lock().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys, vec![limiter.bucket_key(&limited)]);
+    }
+
+    #[tokio::test]
+    async fn dropped_probes_dont_block_the_bucket() {
+        let route = Route::new(&Method::GET, "/channels/123/messages");
+        let limiter = RateLimiter::new();
+
+        let waiting = limiter.run(&route, async { Ok(Response::from(http::Response::new(""))) });
+        tokio::pin!(waiting);
+        tokio::select! {
+            biased;
+            _ = limiter.run(&route, std::future::pending()) => unreachable!(),
+            _ = &mut waiting => panic!("sent while the bucket was being probed"),
+            // Drops the probing request before it got a response.
+            _ = sleep(Duration::from_millis(10)) => {},
+        }
+
+        let res = timeout(Duration::from_secs(1), waiting).await
+            .expect("the waiting request was never woken");
+        assert!(res.is_ok());
+        assert!(!limiter.bucket(&route).state.lock().unwrap().probing);
+    }
+}
@@ -0,0 +1,180 @@
+use rand::Rng;
+use reqwest::{Method, StatusCode};
+use tokio::time::Duration;
+
+/// Decides whether a failed request is sent again, and how long to wait before doing so.
+/// Rate limited requests are always retried (discord doesn't process them),
+/// server errors and transport failures only when the method is idempotent,
+/// unless `retry_non_idempotent` is set.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> RetryPolicy {
+        RetryPolicy::default()
+    }
+
+    /// A policy that sends every request exactly once.
+    pub fn never() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// The total number of times a request may be sent, including the first.
+    pub fn max_attempts(mut self, max_attempts: u32) -> RetryPolicy {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry, which doubles on every attempt after.
+    pub fn base_delay(mut self, base_delay: Duration) -> RetryPolicy {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// The backoff delay will never grow past this.
+    /// Does not apply to delays requested by discord through `Retry-After`.
+    pub fn max_delay(mut self, max_delay: Duration) -> RetryPolicy {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Randomizes each backoff delay to between half and all of itself.
+    pub fn jitter(mut self, jitter: bool) -> RetryPolicy {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Also retry POST and PATCH requests on server errors and transport failures.
+    /// This may cause the request to be applied twice.
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> RetryPolicy {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    /// How long to wait before sending the request again, or None if it shouldn't be.
+    /// `attempt` is the number of the attempt that just failed, starting at 1.
+    /// `status` is None if no response was received.
+    pub(crate) fn retry_delay(
+        &self,
+        attempt: u32,
+        method: &Method,
+        status: Option<StatusCode>,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retryable = self.retry_non_idempotent || is_idempotent(method);
+        match status {
+            Some(StatusCode::TOO_MANY_REQUESTS) => Some(retry_after.unwrap_or_else(|| self.backoff(attempt))),
+            Some(status) if status.is_server_error() && retryable => {
+                Some(retry_after.unwrap_or_else(|| self.backoff(attempt)))
+            },
+            None if retryable => Some(self.backoff(attempt)),
+            _ => None,
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(rand::thread_rng().gen::<f64>())
+        } else {
+            delay
+        }
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS)
+}
+
+/// A failed attempt at sending a request.
+#[derive(Debug, Clone)]
+pub struct RetryAttempt {
+    /// None if no response was received.
+    pub status: Option<StatusCode>,
+    pub error: String,
+    /// How long was waited before the next attempt.
+    pub delay: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new()
+            .max_attempts(20)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(10))
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_delay() {
+        let policy = policy().jitter(false);
+        let delays = (1..8)
+            .map(|attempt| policy.retry_delay(attempt, &Method::GET, None, None).unwrap().as_secs())
+            .collect::<Vec<_>>();
+        assert_eq!(delays, [1, 2, 4, 8, 10, 10, 10]);
+    }
+
+    #[test]
+    fn jitter_stays_between_half_and_all_of_the_delay() {
+        let policy = policy().jitter(true);
+        for attempt in 1..8 {
+            let max = policy.clone().jitter(false).retry_delay(attempt, &Method::GET, None, None).unwrap();
+            for _ in 0..50 {
+                let delay = policy.retry_delay(attempt, &Method::GET, None, None).unwrap();
+                assert!(delay >= max / 2 && delay <= max, "{delay:?} not within {max:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn non_idempotent_requests_are_only_retried_when_rate_limited() {
+        let policy = policy().jitter(false);
+        let retry_after = Some(Duration::from_millis(1500));
+
+        assert_eq!(policy.retry_delay(1, &Method::POST, Some(StatusCode::TOO_MANY_REQUESTS), retry_after), retry_after);
+        assert_eq!(policy.retry_delay(1, &Method::POST, Some(StatusCode::BAD_GATEWAY), None), None);
+        assert_eq!(policy.retry_delay(1, &Method::PATCH, None, None), None);
+
+        assert!(policy.retry_delay(1, &Method::PUT, Some(StatusCode::BAD_GATEWAY), None).is_some());
+        assert!(policy.retry_delay(1, &Method::DELETE, None, None).is_some());
+        assert!(policy.clone().retry_non_idempotent(true).retry_delay(1, &Method::POST, None, None).is_some());
+    }
+
+    #[test]
+    fn client_errors_and_exhausted_attempts_are_not_retried() {
+        let policy = policy().max_attempts(2);
+        assert_eq!(policy.retry_delay(1, &Method::GET, Some(StatusCode::BAD_REQUEST), None), None);
+        assert_eq!(policy.retry_delay(2, &Method::GET, Some(StatusCode::TOO_MANY_REQUESTS), None), None);
+    }
+}
@@ -1,22 +1,38 @@
 #![allow(dead_code)]
+pub mod builders;
 pub mod client;
 pub mod endpoints;
 pub mod http;
 pub(crate) mod api;
 pub mod gateway;
 pub mod model;
+pub mod webhook;
 #[macro_use]
 pub mod serde_utils;
 use core::slice;
-use std::{ops::{Sub, SubAssign}, pin::Pin, task::{Context, Poll}};
+use std::{ops::SubAssign, pin::Pin, task::{Context, Poll}};
 use futures_util::Stream;
 use http::QueryError;
-use model::{channel::{Channel, DmData, GroupDmData}, guild::Guild, message::{DefaultMessageData, GeneralMessageData, Message}, user::{MainUserData, UserData}, Snowflake};
+use model::{channel::{Channel, DmData, GroupDmData, ThreadData, ThreadList}, guild::{audit_log::AuditLogEntry, Ban, BulkBan, Guild, GuildMemberData, GuildPreview, Role}, invite::{invite_code, Invite}, message::{DefaultMessageData, Emoji, Message, MessagePin, ReactionEmoji, ReactionType, Sticker}, scheduled_event::{ScheduledEvent, ScheduledEventUser}, user::{MainUserData, UserData}, webhook::Webhook, Snowflake};
 use pin_project_lite::pin_project;
+use time::OffsetDateTime;
 use tokio::time::Duration;
+use crate::builders::attachment::Attachment;
+use crate::builders::audit_log::AuditLogFilter;
+use crate::builders::emoji::{CreateEmoji, EditEmoji};
+use crate::builders::message::{CreateMessage, EditMessage};
+use crate::builders::forum::{CreateForumPost, CreateForumTag};
+use crate::builders::guild::ModifyGuild;
+use crate::builders::invite::CreateInvite;
+use crate::builders::member::ModifyMember;
+use crate::builders::role::{EditRole, RolePosition};
+use crate::builders::scheduled_event::{CreateScheduledEvent, ModifyScheduledEvent};
+use crate::builders::sticker::{CreateSticker, EditSticker};
+use crate::builders::thread::CreateThread;
+use crate::builders::webhook::{CreateWebhook, ModifyWebhook};
 use crate::client::DiscordClient;
-use api::{DiscordError, Result};
-use async_stream::{stream, try_stream};
+use api::Result;
+use async_stream::try_stream;
 use model::ID;
 
 pub enum MessageSendTime {
@@ -43,6 +59,15 @@ pub enum MessageFetchRate {
     Custom { per_request: u8 }
 }
 
+/// Which archived threads of a channel to list.
+#[derive(Clone, Copy)]
+enum ArchivedThreads {
+    Public,
+    Private,
+    /// Private threads the current user has joined.
+    JoinedPrivate,
+}
+
 pin_project! {
     pub struct MessageStream<'a> {
         #[pin]
@@ -58,6 +83,11 @@ impl<'a> Stream for MessageStream<'a> {
     }
 }
 
+/// For when discord answers with another kind of channel than the one asked for.
+fn structure_mismatch() -> QueryError {
+    QueryError::Other { error: "API return structure mismatch".to_string() }
+}
+
 impl DiscordClient {
     pub async fn me(&self) -> Result<MainUserData> {
         api::get_authenticated_user_data(self.req_client()).await
@@ -71,10 +101,492 @@ impl DiscordClient {
         api::get_private_channels(self.req_client()).await
     }
 
+    /// The guilds the current user is in, these are partial guilds.
     pub async fn guilds(&self) -> Result<Vec<Guild>> {
         api::get_guilds(self.req_client()).await
     }
 
+    /// Fetches a guild in full.
+    /// `with_counts` includes the approximate member and presence counts.
+    pub async fn guild(&self, guild_id: &Snowflake, with_counts: bool) -> Result<Guild> {
+        api::get_guild(self.req_client(), guild_id, with_counts).await
+    }
+
+    /// Works for guilds the current user isn't in if they are discoverable.
+    pub async fn guild_preview(&self, guild_id: &Snowflake) -> Result<GuildPreview> {
+        api::get_guild_preview(self.req_client(), guild_id).await
+    }
+
+    /// Needs the `MANAGE_GUILD` permission.
+    pub async fn modify_guild(
+        &self,
+        guild_id: &Snowflake,
+        guild: ModifyGuild,
+        reason: Option<&str>
+    ) -> Result<Guild> {
+        api::modify_guild(self.req_client(), guild_id, &guild, reason).await
+    }
+
+    /// The guild's roles, including @everyone.
+    pub async fn roles(&self, guild_id: &Snowflake) -> Result<Vec<Role>> {
+        api::roles(self.req_client(), guild_id).await
+    }
+
+    pub async fn role(&self, guild_id: &Snowflake, role_id: &Snowflake) -> Result<Role> {
+        api::role(self.req_client(), guild_id, role_id).await
+    }
+
+    /// Needs the `MANAGE_ROLES` permission.
+    pub async fn create_role(
+        &self,
+        guild_id: &Snowflake,
+        role: EditRole,
+        reason: Option<&str>
+    ) -> Result<Role> {
+        api::create_role(self.req_client(), guild_id, &role, reason).await
+    }
+
+    /// Needs the `MANAGE_ROLES` permission, and the role must be below the user's highest role.
+    pub async fn modify_role(
+        &self,
+        guild_id: &Snowflake,
+        role_id: &Snowflake,
+        role: EditRole,
+        reason: Option<&str>
+    ) -> Result<Role> {
+        api::modify_role(self.req_client(), guild_id, role_id, &role, reason).await
+    }
+
+    /// Moves roles to new positions, the other roles shift to make room.
+    /// Returns every role of the guild.
+    pub async fn reorder_roles(
+        &self,
+        guild_id: &Snowflake,
+        positions: &[RolePosition],
+        reason: Option<&str>
+    ) -> Result<Vec<Role>> {
+        api::reorder_roles(self.req_client(), guild_id, positions, reason).await
+    }
+
+    pub async fn delete_role(
+        &self,
+        guild_id: &Snowflake,
+        role_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::delete_role(self.req_client(), guild_id, role_id, reason).await
+    }
+
+    pub async fn add_member_role(
+        &self,
+        guild_id: &Snowflake,
+        user_id: &Snowflake,
+        role_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::add_member_role(self.req_client(), guild_id, user_id, role_id, reason).await
+    }
+
+    pub async fn remove_member_role(
+        &self,
+        guild_id: &Snowflake,
+        user_id: &Snowflake,
+        role_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::remove_member_role(self.req_client(), guild_id, user_id, role_id, reason).await
+    }
+
+    /// Every member of the guild, fetched 1000 at a time.
+    /// Needs the `GUILD_MEMBERS` privileged intent for bots.
+    pub fn members<'a>(&'a self, guild_id: &'a Snowflake) -> impl Stream<Item = Result<GuildMemberData>> + 'a {
+        let stream = try_stream! {
+            let limit: u16 = 1000;
+            let mut after: Option<Snowflake> = None;
+            loop {
+                let members = api::members(self.req_client(), guild_id, after.as_ref(), limit).await?;
+                let last_page = members.len() < limit as usize;
+                after = members.last()
+                    .and_then(|member| member.user.as_ref())
+                    .map(|user| user.id.clone());
+                for member in members {
+                    yield member;
+                }
+                if last_page || after.is_none() {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    /// Members whose username or nickname starts with the query, up to `limit` (1 to 1000) of them.
+    pub async fn search_members<S: AsRef<str>>(
+        &self,
+        guild_id: &Snowflake,
+        query: S,
+        limit: u16
+    ) -> Result<Vec<GuildMemberData>> {
+        api::search_members(self.req_client(), guild_id, query.as_ref(), limit.clamp(1, 1000)).await
+    }
+
+    pub async fn member(&self, guild_id: &Snowflake, user_id: &Snowflake) -> Result<GuildMemberData> {
+        api::member(self.req_client(), guild_id, user_id).await
+    }
+
+    /// Changes a member's nickname, roles, voice state or timeout.
+    /// Each change needs its own permission, see `ModifyMember`.
+    pub async fn modify_member(
+        &self,
+        guild_id: &Snowflake,
+        user_id: &Snowflake,
+        member: ModifyMember,
+        reason: Option<&str>
+    ) -> Result<GuildMemberData> {
+        api::modify_member(self.req_client(), guild_id, user_id, &member, reason).await
+    }
+
+    /// Needs the `KICK_MEMBERS` permission.
+    pub async fn kick_member(
+        &self,
+        guild_id: &Snowflake,
+        user_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::kick_member(self.req_client(), guild_id, user_id, reason).await
+    }
+
+    /// Bans a user, who doesn't need to be a member.
+    /// Their messages sent in the last `delete_message_seconds` (up to a week) are deleted.
+    /// Needs the `BAN_MEMBERS` permission.
+    pub async fn ban(
+        &self,
+        guild_id: &Snowflake,
+        user_id: &Snowflake,
+        delete_message_seconds: u32,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::ban(self.req_client(), guild_id, user_id, delete_message_seconds, reason).await
+    }
+
+    /// Bans up to 200 users at once.
+    /// Needs the `BAN_MEMBERS` and `MANAGE_GUILD` permissions.
+    pub async fn bulk_ban(
+        &self,
+        guild_id: &Snowflake,
+        user_ids: &[Snowflake],
+        delete_message_seconds: u32,
+        reason: Option<&str>
+    ) -> Result<BulkBan> {
+        api::bulk_ban(self.req_client(), guild_id, user_ids, delete_message_seconds, reason).await
+    }
+
+    pub async fn unban(
+        &self,
+        guild_id: &Snowflake,
+        user_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::unban(self.req_client(), guild_id, user_id, reason).await
+    }
+
+    /// Every ban in the guild, fetched 1000 at a time.
+    pub fn bans<'a>(&'a self, guild_id: &'a Snowflake) -> impl Stream<Item = Result<Ban>> + 'a {
+        let stream = try_stream! {
+            let limit: u16 = 1000;
+            let mut after: Option<Snowflake> = None;
+            loop {
+                let bans = api::bans(self.req_client(), guild_id, after.as_ref(), limit).await?;
+                let last_page = bans.len() < limit as usize;
+                after = bans.last().map(|ban| ban.user.id.clone());
+                for ban in bans {
+                    yield ban;
+                }
+                if last_page {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    pub async fn get_ban(&self, guild_id: &Snowflake, user_id: &Snowflake) -> Result<Ban> {
+        api::get_ban(self.req_client(), guild_id, user_id).await
+    }
+
+    /// The guild's audit log, newest first, fetched 100 entries at a time.
+    /// With `AuditLogFilter::after`, the entries are listed oldest first instead.
+    /// Needs the `VIEW_AUDIT_LOG` permission.
+    pub fn audit_log<'a>(
+        &'a self,
+        guild_id: &'a Snowflake,
+        filter: AuditLogFilter
+    ) -> impl Stream<Item = Result<AuditLogEntry>> + 'a {
+        let stream = try_stream! {
+            let limit: u8 = 100;
+            let oldest_first = filter.after.is_some();
+            let mut before = filter.before;
+            let mut after = filter.after;
+            loop {
+                let mut entries = api::audit_log(
+                    self.req_client(),
+                    guild_id,
+                    filter.user_id.as_ref(),
+                    filter.action_type,
+                    before.as_ref(),
+                    after.as_ref(),
+                    limit
+                ).await?.into_entries();
+                let last_page = entries.len() < limit as usize;
+
+                entries.sort_by_key(|entry| entry.id.as_u64());
+                if oldest_first {
+                    after = entries.last().map(|entry| entry.id.clone());
+                } else {
+                    entries.reverse();
+                    before = entries.last().map(|entry| entry.id.clone());
+                }
+                for entry in entries {
+                    yield entry;
+                }
+                if last_page || (before.is_none() && after.is_none()) {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    /// Needs the `CREATE_INSTANT_INVITE` permission.
+    pub async fn create_invite(
+        &self,
+        channel_id: &Snowflake,
+        invite: CreateInvite,
+        reason: Option<&str>
+    ) -> Result<Invite> {
+        api::create_invite(self.req_client(), channel_id, &invite, reason).await
+    }
+
+    /// Looks up an invite from its code or url (e.g. `https://discord.gg/code`).
+    /// `with_counts` includes the guild's approximate member and presence counts.
+    pub async fn resolve_invite<S: AsRef<str>>(&self, code_or_url: S, with_counts: bool) -> Result<Invite> {
+        api::resolve_invite(self.req_client(), invite_code(code_or_url.as_ref()), with_counts).await
+    }
+
+    /// Needs the `MANAGE_CHANNELS` permission.
+    pub async fn channel_invites(&self, channel_id: &Snowflake) -> Result<Vec<Invite>> {
+        api::channel_invites(self.req_client(), channel_id).await
+    }
+
+    /// Needs the `MANAGE_GUILD` permission.
+    pub async fn guild_invites(&self, guild_id: &Snowflake) -> Result<Vec<Invite>> {
+        api::guild_invites(self.req_client(), guild_id).await
+    }
+
+    /// Revokes an invite, returning it.
+    /// Needs the `MANAGE_CHANNELS` permission on its channel, or `MANAGE_GUILD`.
+    pub async fn delete_invite<S: AsRef<str>>(&self, code_or_url: S, reason: Option<&str>) -> Result<Invite> {
+        api::delete_invite(self.req_client(), invite_code(code_or_url.as_ref()), reason).await
+    }
+
+    /// Joins the guild or group dm an invite leads to.
+    /// Only works for user accounts, bots are added to guilds through OAuth2.
+    pub async fn accept_invite<S: AsRef<str>>(&self, code_or_url: S) -> Result<Invite> {
+        api::accept_invite(self.req_client(), invite_code(code_or_url.as_ref())).await
+    }
+
+    /// The guild's custom emojis.
+    pub async fn guild_emojis(&self, guild_id: &Snowflake) -> Result<Vec<Emoji>> {
+        api::guild_emojis(self.req_client(), guild_id).await
+    }
+
+    pub async fn guild_emoji(&self, guild_id: &Snowflake, emoji_id: &Snowflake) -> Result<Emoji> {
+        api::guild_emoji(self.req_client(), guild_id, emoji_id).await
+    }
+
+    /// Needs the `CREATE_GUILD_EXPRESSIONS` permission.
+    pub async fn create_emoji(
+        &self,
+        guild_id: &Snowflake,
+        emoji: CreateEmoji,
+        reason: Option<&str>
+    ) -> Result<Emoji> {
+        api::create_emoji(self.req_client(), guild_id, &emoji, reason).await
+    }
+
+    /// Needs the `MANAGE_GUILD_EXPRESSIONS` permission,
+    /// or `CREATE_GUILD_EXPRESSIONS` for emojis the current user uploaded.
+    pub async fn modify_emoji(
+        &self,
+        guild_id: &Snowflake,
+        emoji_id: &Snowflake,
+        emoji: EditEmoji,
+        reason: Option<&str>
+    ) -> Result<Emoji> {
+        api::modify_emoji(self.req_client(), guild_id, emoji_id, &emoji, reason).await
+    }
+
+    /// Needs the `MANAGE_GUILD_EXPRESSIONS` permission,
+    /// or `CREATE_GUILD_EXPRESSIONS` for emojis the current user uploaded.
+    pub async fn delete_emoji(&self, guild_id: &Snowflake, emoji_id: &Snowflake, reason: Option<&str>) -> Result<()> {
+        api::delete_emoji(self.req_client(), guild_id, emoji_id, reason).await
+    }
+
+    pub async fn guild_stickers(&self, guild_id: &Snowflake) -> Result<Vec<Sticker>> {
+        api::guild_stickers(self.req_client(), guild_id).await
+    }
+
+    pub async fn guild_sticker(&self, guild_id: &Snowflake, sticker_id: &Snowflake) -> Result<Sticker> {
+        api::guild_sticker(self.req_client(), guild_id, sticker_id).await
+    }
+
+    /// Needs the `CREATE_GUILD_EXPRESSIONS` permission.
+    pub async fn create_sticker(
+        &self,
+        guild_id: &Snowflake,
+        sticker: CreateSticker,
+        reason: Option<&str>
+    ) -> Result<Sticker> {
+        api::create_sticker(self.req_client(), guild_id, sticker, reason).await
+    }
+
+    /// Needs the `MANAGE_GUILD_EXPRESSIONS` permission,
+    /// or `CREATE_GUILD_EXPRESSIONS` for stickers the current user uploaded.
+    pub async fn modify_sticker(
+        &self,
+        guild_id: &Snowflake,
+        sticker_id: &Snowflake,
+        sticker: EditSticker,
+        reason: Option<&str>
+    ) -> Result<Sticker> {
+        api::modify_sticker(self.req_client(), guild_id, sticker_id, &sticker, reason).await
+    }
+
+    /// Needs the `MANAGE_GUILD_EXPRESSIONS` permission,
+    /// or `CREATE_GUILD_EXPRESSIONS` for stickers the current user uploaded.
+    pub async fn delete_sticker(&self, guild_id: &Snowflake, sticker_id: &Snowflake, reason: Option<&str>) -> Result<()> {
+        api::delete_sticker(self.req_client(), guild_id, sticker_id, reason).await
+    }
+
+    /// The guild's scheduled events that haven't ended yet.
+    /// `with_user_count` includes how many users are subscribed to each event.
+    pub async fn scheduled_events(&self, guild_id: &Snowflake, with_user_count: bool) -> Result<Vec<ScheduledEvent>> {
+        api::scheduled_events(self.req_client(), guild_id, with_user_count).await
+    }
+
+    pub async fn scheduled_event(
+        &self,
+        guild_id: &Snowflake,
+        event_id: &Snowflake,
+        with_user_count: bool
+    ) -> Result<ScheduledEvent> {
+        api::scheduled_event(self.req_client(), guild_id, event_id, with_user_count).await
+    }
+
+    /// Needs the `CREATE_EVENTS` permission, and the channel permissions to join
+    /// or speak in the event's channel.
+    pub async fn create_scheduled_event(
+        &self,
+        guild_id: &Snowflake,
+        event: CreateScheduledEvent,
+        reason: Option<&str>
+    ) -> Result<ScheduledEvent> {
+        api::create_scheduled_event(self.req_client(), guild_id, &event, reason).await
+    }
+
+    /// Needs the `MANAGE_EVENTS` permission,
+    /// or `CREATE_EVENTS` for events the current user created.
+    pub async fn modify_scheduled_event(
+        &self,
+        guild_id: &Snowflake,
+        event_id: &Snowflake,
+        event: ModifyScheduledEvent,
+        reason: Option<&str>
+    ) -> Result<ScheduledEvent> {
+        api::modify_scheduled_event(self.req_client(), guild_id, event_id, &event, reason).await
+    }
+
+    /// Needs the `MANAGE_EVENTS` permission,
+    /// or `CREATE_EVENTS` for events the current user created.
+    pub async fn delete_scheduled_event(&self, guild_id: &Snowflake, event_id: &Snowflake) -> Result<()> {
+        api::delete_scheduled_event(self.req_client(), guild_id, event_id).await
+    }
+
+    /// The users subscribed to a scheduled event, 100 per request.
+    /// `with_member` includes their guild member data.
+    pub fn scheduled_event_users<'a>(
+        &'a self,
+        guild_id: &'a Snowflake,
+        event_id: &'a Snowflake,
+        with_member: bool
+    ) -> impl Stream<Item = Result<ScheduledEventUser>> + 'a {
+        let stream = try_stream! {
+            let limit: u8 = 100;
+            let mut after: Option<Snowflake> = None;
+            loop {
+                let users = api::scheduled_event_users(self.req_client(), guild_id, event_id, after.as_ref(), limit, with_member).await?;
+                let last_page = users.len() < limit as usize;
+                after = users.last().map(|user| user.user.id.clone());
+                for user in users {
+                    yield user;
+                }
+                if last_page || after.is_none() {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    /// Needs the `MANAGE_WEBHOOKS` permission.
+    pub async fn create_webhook(
+        &self,
+        channel_id: &Snowflake,
+        webhook: CreateWebhook,
+        reason: Option<&str>
+    ) -> Result<Webhook> {
+        api::create_webhook(self.req_client(), channel_id, &webhook, reason).await
+    }
+
+    /// Needs the `MANAGE_WEBHOOKS` permission.
+    pub async fn channel_webhooks(&self, channel_id: &Snowflake) -> Result<Vec<Webhook>> {
+        api::channel_webhooks(self.req_client(), channel_id).await
+    }
+
+    /// Needs the `MANAGE_WEBHOOKS` permission.
+    pub async fn guild_webhooks(&self, guild_id: &Snowflake) -> Result<Vec<Webhook>> {
+        api::guild_webhooks(self.req_client(), guild_id).await
+    }
+
+    /// Needs the `MANAGE_WEBHOOKS` permission.
+    pub async fn webhook(&self, webhook_id: &Snowflake) -> Result<Webhook> {
+        api::get_webhook(self.req_client(), webhook_id).await
+    }
+
+    /// Needs the `MANAGE_WEBHOOKS` permission, in the new channel too if it's moved.
+    pub async fn modify_webhook(
+        &self,
+        webhook_id: &Snowflake,
+        webhook: ModifyWebhook,
+        reason: Option<&str>
+    ) -> Result<Webhook> {
+        api::modify_webhook(self.req_client(), webhook_id, &webhook, reason).await
+    }
+
+    /// Needs the `MANAGE_WEBHOOKS` permission.
+    pub async fn delete_webhook(&self, webhook_id: &Snowflake, reason: Option<&str>) -> Result<()> {
+        api::delete_webhook(self.req_client(), webhook_id, reason).await
+    }
+
+    /// The guild's channels, without threads.
+    pub async fn guild_channels(&self, guild_id: &Snowflake) -> Result<Vec<Channel>> {
+        api::get_channels_in_guild(self.req_client(), guild_id).await
+    }
+
     pub fn messages<'a>(
         &'a self, 
         channel_id: &'a Snowflake, 
@@ -110,10 +622,355 @@ impl DiscordClient {
         }
     }
 
-    pub async fn send_message<S: AsRef<str>>(&self, channel_id: &Snowflake, content: S, send_time: MessageSendTime) -> Result<DefaultMessageData> {
-        let content = content.as_ref();
+    /// Sends a message, which can be a plain string or a `CreateMessage`.
+    pub async fn send_message<M: Into<CreateMessage>>(
+        &self,
+        channel_id: &Snowflake,
+        message: M,
+        send_time: MessageSendTime
+    ) -> Result<DefaultMessageData> {
+        let message = message.into();
+        self.simulate_typing(channel_id, message.content_str(), send_time).await?;
+        api::send_message(self.req_client(), channel_id, message).await
+    }
+
+    /// Sends a message with files attached to it.
+    /// The content may be empty if there is at least one attachment.
+    pub async fn send_files<S: AsRef<str>>(
+        &self,
+        channel_id: &Snowflake,
+        content: S,
+        attachments: Vec<Attachment>,
+        send_time: MessageSendTime
+    ) -> Result<DefaultMessageData> {
+        let message = CreateMessage::new()
+            .content(content)
+            .attachments(attachments);
+        self.send_message(channel_id, message, send_time).await
+    }
+
+    /// Edits a message, which can be a plain string to replace the content with or an `EditMessage`.
+    pub async fn edit_message<M: Into<EditMessage>>(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        message: M
+    ) -> Result<Message> {
+        api::edit_message(self.req_client(), channel_id, message_id, message.into()).await
+    }
+
+    /// Deleting another user's message needs the `MANAGE_MESSAGES` permission,
+    /// the reason shows up in the guild's audit log.
+    pub async fn delete_message(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::delete_message(self.req_client(), channel_id, message_id, reason).await
+    }
+
+    /// Deletes 2 to 100 messages at once, none of which can be older than 14 days.
+    /// Those limits are checked before sending the request.
+    pub async fn bulk_delete_messages(
+        &self,
+        channel_id: &Snowflake,
+        message_ids: &[Snowflake],
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::bulk_delete_messages(self.req_client(), channel_id, message_ids, reason).await
+    }
+
+    /// Publishes a message in an announcement channel to the channels following it.
+    pub async fn crosspost_message(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake
+    ) -> Result<Message> {
+        api::crosspost_message(self.req_client(), channel_id, message_id).await
+    }
+
+    /// Reacts to a message with a unicode emoji (e.g. "🔥") or a custom `Emoji`.
+    pub async fn add_reaction<E: Into<ReactionEmoji>>(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        emoji: E
+    ) -> Result<()> {
+        api::add_reaction(self.req_client(), channel_id, message_id, &emoji.into()).await
+    }
+
+    pub async fn remove_own_reaction<E: Into<ReactionEmoji>>(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        emoji: E
+    ) -> Result<()> {
+        api::remove_reaction(self.req_client(), channel_id, message_id, &emoji.into(), None).await
+    }
+
+    /// Needs the `MANAGE_MESSAGES` permission.
+    pub async fn remove_user_reaction<E: Into<ReactionEmoji>>(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        emoji: E,
+        user_id: &Snowflake
+    ) -> Result<()> {
+        api::remove_reaction(self.req_client(), channel_id, message_id, &emoji.into(), Some(user_id)).await
+    }
+
+    /// Needs the `MANAGE_MESSAGES` permission.
+    pub async fn remove_all_reactions(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake
+    ) -> Result<()> {
+        api::remove_all_reactions(self.req_client(), channel_id, message_id).await
+    }
+
+    /// Removes every reaction with the emoji. Needs the `MANAGE_MESSAGES` permission.
+    pub async fn remove_emoji_reactions<E: Into<ReactionEmoji>>(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        emoji: E
+    ) -> Result<()> {
+        api::remove_emoji_reactions(self.req_client(), channel_id, message_id, &emoji.into()).await
+    }
+
+    /// The users who reacted to a message with the emoji, fetched 100 at a time.
+    /// Normal and burst reactions are listed separately.
+    pub fn reactors<'a, E: Into<ReactionEmoji>>(
+        &'a self,
+        channel_id: &'a Snowflake,
+        message_id: &'a Snowflake,
+        emoji: E,
+        reaction_type: ReactionType
+    ) -> impl Stream<Item = Result<UserData>> + 'a {
+        let emoji = emoji.into();
+        let stream = try_stream! {
+            let limit: u8 = 100;
+            let mut after: Option<Snowflake> = None;
+            loop {
+                let users = api::reactors(
+                    self.req_client(),
+                    channel_id,
+                    message_id,
+                    &emoji,
+                    reaction_type,
+                    after.as_ref(),
+                    limit
+                ).await?;
+                let last_page = users.len() < limit as usize;
+                after = users.last().map(|user| user.id.clone());
+                for user in users {
+                    yield user;
+                }
+                if last_page {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    /// The channel's pinned messages, up to 50 of them.
+    /// `pins` pages through every pin, with the time each message was pinned.
+    pub async fn pinned_messages(&self, channel_id: &Snowflake) -> Result<Vec<Message>> {
+        api::pinned_messages(self.req_client(), channel_id).await
+    }
+
+    /// Every pin in the channel, newest first, fetched 50 at a time.
+    pub fn pins<'a>(&'a self, channel_id: &'a Snowflake) -> impl Stream<Item = Result<MessagePin>> + 'a {
+        let stream = try_stream! {
+            let limit: u8 = 50;
+            let mut before: Option<OffsetDateTime> = None;
+            loop {
+                let page = api::pins(self.req_client(), channel_id, before, limit).await?;
+                before = page.items.last().map(|pin| pin.pinned_at);
+                for pin in page.items {
+                    yield pin;
+                }
+                if !page.has_more || before.is_none() {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    /// Needs the `PIN_MESSAGES` permission in guilds.
+    /// A channel can have at most 250 pins.
+    pub async fn pin_message(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::pin_message(self.req_client(), channel_id, message_id, reason).await
+    }
+
+    pub async fn unpin_message(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        reason: Option<&str>
+    ) -> Result<()> {
+        api::unpin_message(self.req_client(), channel_id, message_id, reason).await
+    }
+
+    /// Starts a thread from a message, the thread shares the message's id.
+    pub async fn start_thread_from_message(
+        &self,
+        channel_id: &Snowflake,
+        message_id: &Snowflake,
+        thread: CreateThread,
+        reason: Option<&str>
+    ) -> Result<ThreadData> {
+        api::start_thread_from_message(self.req_client(), channel_id, message_id, &thread, reason).await?
+            .into_thread()
+            .ok_or_else(structure_mismatch)
+    }
+
+    /// Starts a thread that isn't attached to a message.
+    pub async fn start_thread(
+        &self,
+        channel_id: &Snowflake,
+        thread: CreateThread,
+        reason: Option<&str>
+    ) -> Result<ThreadData> {
+        api::start_thread(self.req_client(), channel_id, &thread, reason).await?
+            .into_thread()
+            .ok_or_else(structure_mismatch)
+    }
+
+    /// The thread can't be archived.
+    pub async fn join_thread(&self, thread_id: &Snowflake) -> Result<()> {
+        api::add_thread_member(self.req_client(), thread_id, None).await
+    }
+
+    pub async fn leave_thread(&self, thread_id: &Snowflake) -> Result<()> {
+        api::remove_thread_member(self.req_client(), thread_id, None).await
+    }
+
+    /// Needs to be able to send messages in the thread, and the thread can't be archived.
+    pub async fn add_thread_member(&self, thread_id: &Snowflake, user_id: &Snowflake) -> Result<()> {
+        api::add_thread_member(self.req_client(), thread_id, Some(user_id)).await
+    }
+
+    /// Needs the `MANAGE_THREADS` permission, unless the current user created the private thread.
+    pub async fn remove_thread_member(&self, thread_id: &Snowflake, user_id: &Snowflake) -> Result<()> {
+        api::remove_thread_member(self.req_client(), thread_id, Some(user_id)).await
+    }
+
+    /// The channel's archived public threads, most recently archived first.
+    pub fn archived_public_threads<'a>(&'a self, channel_id: &'a Snowflake) -> impl Stream<Item = Result<ThreadData>> + 'a {
+        self.archived_threads(channel_id, ArchivedThreads::Public)
+    }
+
+    /// The channel's archived private threads, most recently archived first.
+    /// Needs the `MANAGE_THREADS` permission.
+    pub fn archived_private_threads<'a>(&'a self, channel_id: &'a Snowflake) -> impl Stream<Item = Result<ThreadData>> + 'a {
+        self.archived_threads(channel_id, ArchivedThreads::Private)
+    }
+
+    /// The channel's archived private threads the current user has joined, newest first.
+    pub fn joined_archived_private_threads<'a>(&'a self, channel_id: &'a Snowflake) -> impl Stream<Item = Result<ThreadData>> + 'a {
+        self.archived_threads(channel_id, ArchivedThreads::JoinedPrivate)
+    }
+
+    fn archived_threads<'a>(&'a self, channel_id: &'a Snowflake, kind: ArchivedThreads) -> impl Stream<Item = Result<ThreadData>> + 'a {
+        let stream = try_stream! {
+            let limit: u8 = 100;
+            let mut before_timestamp: Option<OffsetDateTime> = None;
+            let mut before_id: Option<Snowflake> = None;
+            loop {
+                let page: ThreadList = match kind {
+                    ArchivedThreads::Public => api::public_archived_threads(
+                        self.req_client(), channel_id, before_timestamp, limit
+                    ).await?,
+                    ArchivedThreads::Private => api::private_archived_threads(
+                        self.req_client(), channel_id, before_timestamp, limit
+                    ).await?,
+                    ArchivedThreads::JoinedPrivate => api::joined_private_archived_threads(
+                        self.req_client(), channel_id, before_id.as_ref(), limit
+                    ).await?,
+                };
+                let has_more = page.has_more;
+                let threads = page.into_threads();
+                if threads.is_empty() {
+                    break;
+                }
+
+                for thread in threads {
+                    before_timestamp = Some(thread.thread_metadata.archive_timestamp);
+                    before_id = Some(thread.id.clone());
+                    yield thread;
+                }
+                if !has_more {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    /// The guild's active threads, including forum and media posts.
+    pub async fn active_threads(&self, guild_id: &Snowflake) -> Result<Vec<ThreadData>> {
+        Ok(api::active_threads(self.req_client(), guild_id).await?.into_threads())
+    }
+
+    /// The active posts of a forum or media channel, with the tags applied to them.
+    pub async fn active_posts(&self, guild_id: &Snowflake, channel_id: &Snowflake) -> Result<Vec<ThreadData>> {
+        let mut threads = self.active_threads(guild_id).await?;
+        threads.retain(|thread| thread.parent_id.as_ref() == Some(channel_id));
+        Ok(threads)
+    }
+
+    /// Creates a post in a forum or media channel.
+    /// The post is a thread, its starter message shares its id.
+    pub async fn create_forum_post(
+        &self,
+        channel_id: &Snowflake,
+        post: CreateForumPost,
+        reason: Option<&str>
+    ) -> Result<ThreadData> {
+        api::create_forum_post(self.req_client(), channel_id, post, reason).await?
+            .into_thread()
+            .ok_or_else(structure_mismatch)
+    }
+
+    /// Replaces the tags applied to a forum or media post. Up to 5.
+    pub async fn set_post_tags(
+        &self,
+        post_id: &Snowflake,
+        tag_ids: &[Snowflake],
+        reason: Option<&str>
+    ) -> Result<ThreadData> {
+        api::set_applied_tags(self.req_client(), post_id, tag_ids, reason).await?
+            .into_thread()
+            .ok_or_else(structure_mismatch)
+    }
+
+    /// Replaces the set of tags a forum or media channel offers. Up to 20.
+    /// Existing tags are kept by converting them from their `GuildForumTag`, any left out are deleted.
+    pub async fn set_forum_tags(
+        &self,
+        channel_id: &Snowflake,
+        tags: &[CreateForumTag],
+        reason: Option<&str>
+    ) -> Result<Channel> {
+        api::set_available_tags(self.req_client(), channel_id, tags, reason).await
+    }
+
+    /// Shows the user as typing for as long as the send time takes to type the content.
+    async fn simulate_typing(&self, channel_id: &Snowflake, content: &str, send_time: MessageSendTime) -> Result<()> {
         let mut typing_duration = match send_time {
-            MessageSendTime::Instant => return api::send_message(self.req_client(), channel_id, content).await,
+            MessageSendTime::Instant => return Ok(()),
             MessageSendTime::Fast => Duration::from_secs_f32(content.len() as f32 / 15_f32),
             MessageSendTime::Medium => Duration::from_secs_f32(content.len() as f32 / 10_f32),
             MessageSendTime::Slow => Duration::from_secs_f32(content.len() as f32 / 5_f32),
@@ -133,7 +990,7 @@ impl DiscordClient {
             }
         }
 
-        api::send_message(self.req_client(), channel_id, content).await
+        Ok(())
     }
 
     // TODO! untested
@@ -152,7 +1009,7 @@ impl DiscordClient {
     ) -> Result<DmData> {
         match api::open_channel(self.req_client(), slice::from_ref(recipient_id)).await? {
             Channel::Dm(d) => Ok(d),
-            _ => Err(QueryError::Other { error: "API return structure mismatch".to_string() })
+            _ => Err(structure_mismatch())
         }
     }
     
@@ -163,7 +1020,7 @@ impl DiscordClient {
     ) -> Result<GroupDmData> {
         match api::open_channel(self.req_client(), recipient_ids).await? {
             Channel::GroupDm(d) => Ok(d),
-            _ => Err(QueryError::Other { error: "API return structure mismatch".to_string() })
+            _ => Err(structure_mismatch())
         }
     }
     
@@ -181,4 +1038,215 @@ impl DefaultMessageData {
     pub fn is_author(&self, user_id: &Snowflake) -> bool {
         user_id == &self.author.id
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+    use crate::http::mock::{MockApi, ME};
+    use crate::model::guild::audit_log::EventType;
+    use super::*;
+
+    fn user(id: u64) -> String {
+        format!(r#"{{"id":"{id}","username":"user{id}","avatar":null,"discriminator":"0"}}"#)
+    }
+
+    fn member(id: u64) -> String {
+        format!(
+            r#"{{"avatar":null,"deaf":false,"flags":0,"joined_at":"2024-01-01T00:00:00+00:00","mute":false,"nick":null,"premium_since":null,"roles":[],"user":{}}}"#,
+            user(id)
+        )
+    }
+
+    fn ban(id: u64) -> String {
+        format!(r#"{{"reason":null,"user":{}}}"#, user(id))
+    }
+
+    fn audit_log_entry(id: u64) -> String {
+        format!(r#"{{"id":"{id}","action_type":22,"user_id":"1","target_id":"2","reason":null}}"#)
+    }
+
+    fn scheduled_event_user(id: u64) -> String {
+        format!(r#"{{"guild_scheduled_event_id":"9","user":{}}}"#, user(id))
+    }
+
+    fn thread(id: u64, archived_at: &str) -> String {
+        format!(
+            r#"{{"id":"{id}","guild_id":"1","parent_id":"2","owner_id":"3","name":"thread {id}","type":11,"last_message_id":null,"thread_metadata":{{"archived":true,"auto_archive_duration":1440,"archive_timestamp":"{archived_at}","locked":false}}}}"#
+        )
+    }
+
+    fn page<I: IntoIterator<Item = String>>(items: I) -> String {
+        format!("[{}]", items.into_iter().collect::<Vec<_>>().join(","))
+    }
+
+    fn audit_log_page<I: IntoIterator<Item = u64>>(ids: I) -> String {
+        format!(r#"{{"audit_log_entries":{},"users":[{}]}}"#, page(ids.into_iter().map(audit_log_entry)), user(1))
+    }
+
+    fn thread_page(threads: Vec<String>, has_more: bool) -> String {
+        format!(r#"{{"threads":{},"members":[],"has_more":{has_more}}}"#, page(threads))
+    }
+
+    /// The requests made after the client fetched the current user.
+    fn page_requests(api: &MockApi) -> Vec<String> {
+        api.requests().split_off(1)
+    }
+
+    fn ids<I: IntoIterator<Item = Snowflake>>(ids: I) -> Vec<u64> {
+        ids.into_iter().map(|id| id.as_u64().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn members_are_fetched_after_the_last_member_until_a_short_page() {
+        let api = MockApi::start([
+            ME.to_string(),
+            page((1..=1000).map(member)),
+            page((1001..=1002).map(member)),
+        ]).await;
+        let client = api.client().await;
+
+        let members: Vec<_> = client.members(&Snowflake::new("1")).try_collect().await.unwrap();
+
+        assert_eq!(ids(members.into_iter().filter_map(|member| member.user).map(|user| user.id)), (1..=1002).collect::<Vec<_>>());
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/guilds/1/members?limit=1000",
+            "GET /api/v10/guilds/1/members?after=1000&limit=1000",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn bans_end_at_an_empty_page() {
+        let api = MockApi::start([
+            ME.to_string(),
+            page((1..=1000).map(ban)),
+            page([]),
+        ]).await;
+        let client = api.client().await;
+
+        let bans: Vec<_> = client.bans(&Snowflake::new("1")).try_collect().await.unwrap();
+
+        assert_eq!(ids(bans.into_iter().map(|ban| ban.user.id)), (1..=1000).collect::<Vec<_>>());
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/guilds/1/bans?limit=1000",
+            "GET /api/v10/guilds/1/bans?after=1000&limit=1000",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn reactors_are_fetched_after_the_last_user() {
+        let api = MockApi::start([
+            ME.to_string(),
+            page((1..=100).map(user)),
+            page([user(101)]),
+        ]).await;
+        let client = api.client().await;
+
+        let users: Vec<_> = client.reactors(&Snowflake::new("1"), &Snowflake::new("2"), "🔥", ReactionType::Normal)
+            .try_collect().await
+            .unwrap();
+
+        assert_eq!(ids(users.into_iter().map(|user| user.id)), (1..=101).collect::<Vec<_>>());
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/channels/1/messages/2/reactions/%F0%9F%94%A5?type=0&limit=100",
+            "GET /api/v10/channels/1/messages/2/reactions/%F0%9F%94%A5?type=0&after=100&limit=100",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn audit_log_pages_backwards_from_the_oldest_entry() {
+        // Discord doesn't promise an order within a page.
+        let api = MockApi::start([
+            ME.to_string(),
+            audit_log_page((101..=200).rev().step_by(2).chain((101..=200).step_by(2))),
+            audit_log_page([2, 3, 1]),
+        ]).await;
+        let client = api.client().await;
+
+        let entries: Vec<_> = client.audit_log(&Snowflake::new("1"), AuditLogFilter::new().action_type(EventType::MemberBanAdd))
+            .try_collect().await
+            .unwrap();
+
+        assert_eq!(ids(entries.iter().map(|entry| entry.id.clone())), (1..=3).chain(101..=200).rev().collect::<Vec<_>>());
+        assert!(entries.iter().all(|entry| entry.user.as_ref().is_some_and(|user| user.id == Snowflake::new("1"))));
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/guilds/1/audit-logs?limit=100&action_type=22",
+            "GET /api/v10/guilds/1/audit-logs?limit=100&action_type=22&before=101",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn audit_log_after_an_entry_pages_forwards() {
+        let api = MockApi::start([
+            ME.to_string(),
+            audit_log_page((51..=150).rev()),
+            audit_log_page([]),
+        ]).await;
+        let client = api.client().await;
+
+        let entries: Vec<_> = client.audit_log(&Snowflake::new("1"), AuditLogFilter::new().after(&Snowflake::new("50")))
+            .try_collect().await
+            .unwrap();
+
+        assert_eq!(ids(entries.into_iter().map(|entry| entry.id)), (51..=150).collect::<Vec<_>>());
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/guilds/1/audit-logs?limit=100&after=50",
+            "GET /api/v10/guilds/1/audit-logs?limit=100&after=150",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn scheduled_event_users_are_fetched_after_the_last_user() {
+        let api = MockApi::start([
+            ME.to_string(),
+            page((1..=100).map(scheduled_event_user)),
+            page((101..=150).map(scheduled_event_user)),
+        ]).await;
+        let client = api.client().await;
+
+        let users: Vec<_> = client.scheduled_event_users(&Snowflake::new("1"), &Snowflake::new("9"), false)
+            .try_collect().await
+            .unwrap();
+
+        assert_eq!(ids(users.into_iter().map(|user| user.user.id)), (1..=150).collect::<Vec<_>>());
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/guilds/1/scheduled-events/9/users?limit=100&with_member=false",
+            "GET /api/v10/guilds/1/scheduled-events/9/users?after=100&limit=100&with_member=false",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn archived_threads_page_until_there_are_no_more() {
+        let api = MockApi::start([
+            ME.to_string(),
+            thread_page(vec![thread(3, "2024-03-01T12:00:00+00:00"), thread(2, "2024-02-01T12:00:00+00:00")], true),
+            thread_page(vec![thread(1, "2024-01-01T12:00:00+00:00")], false),
+        ]).await;
+        let client = api.client().await;
+
+        let threads: Vec<_> = client.archived_public_threads(&Snowflake::new("2")).try_collect().await.unwrap();
+
+        assert_eq!(ids(threads.into_iter().map(|thread| thread.id)), [3, 2, 1]);
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/channels/2/threads/archived/public?limit=100",
+            "GET /api/v10/channels/2/threads/archived/public?before=2024%2D02%2D01T12%3A00%3A00Z&limit=100",
+        ]);
+    }
+
+    #[tokio::test]
+    async fn joined_archived_threads_page_by_thread_id() {
+        let api = MockApi::start([
+            ME.to_string(),
+            thread_page(vec![thread(3, "2024-03-01T12:00:00+00:00"), thread(2, "2024-02-01T12:00:00+00:00")], true),
+            thread_page(vec![], true),
+        ]).await;
+        let client = api.client().await;
+
+        let threads: Vec<_> = client.joined_archived_private_threads(&Snowflake::new("2")).try_collect().await.unwrap();
+
+        assert_eq!(ids(threads.into_iter().map(|thread| thread.id)), [3, 2]);
+        assert_eq!(page_requests(&api), [
+            "GET /api/v10/channels/2/users/@me/threads/archived/private?limit=100",
+            "GET /api/v10/channels/2/users/@me/threads/archived/private?before=2&limit=100",
+        ]);
+    }
+}
@@ -1,32 +1,58 @@
-use async_std::channel;
 use num::FromPrimitive;
 use num_derive::FromPrimitive;
 use serde::Deserialize;
 use serde_json::Value;
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use time::OffsetDateTime;
-use crate::http::QueryError;
 use crate::serde_utils;
 use crate::model;
 use serde::de::Error;
 use model::{permissions::PermissionOverwrite, user::*};
 
-use super::guild;
 use super::Snowflake;
 use super::ID;
 
-#[derive(Deserialize, Debug)]
+/// A tag that can be applied to the posts of a forum or media channel.
+#[derive(Deserialize, Debug, Clone)]
 pub struct GuildForumTag {
-    id: Snowflake, 
-    name: String,
-    moderated: bool,
-    emoji_id: Option<Snowflake>,
-    emoji_name: Option<String>
+    pub id: Snowflake,
+    pub name: String,
+    /// Only users with `MANAGE_THREADS` can apply moderated tags.
+    pub moderated: bool,
+    /// Set for custom emojis.
+    pub emoji_id: Option<Snowflake>,
+    /// Set for unicode emojis.
+    pub emoji_name: Option<String>
+}
+
+/// The emoji shown in the add reaction button of forum and media posts.
+#[derive(Deserialize, Debug, Clone)]
+pub struct DefaultReaction {
+    pub emoji_id: Option<Snowflake>,
+    pub emoji_name: Option<String>,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SortOrderType {
+    LatestActivity = 0,
+    CreationDate = 1,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum ForumLayoutType {
+    #[default]
+    NotSet = 0,
+    ListView = 1,
+    GalleryView = 2,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct GuildTextData {
     pub id: Snowflake,
     pub last_message_id: Option<Snowflake>,
+    #[serde(default)]
     pub flags: u64,
     pub guild_id: Snowflake,
     #[serde(default, with = "time::serde::iso8601::option")]
@@ -34,10 +60,12 @@ pub struct GuildTextData {
     pub name: String,
     #[serde(rename = "parent_id")]
     pub category_id: Option<Snowflake>,
+    #[serde(default)]
     pub rate_limit_per_user: u32,
     pub topic: Option<String>,
     pub position: u32,
     pub permission_overwrites: Vec<PermissionOverwrite>,
+    #[serde(default)]
     pub nsfw: bool,
 }
 
@@ -56,6 +84,7 @@ pub struct DmData {
 pub struct GuildVoiceData {
     pub id: Snowflake,
     pub last_message_id: Option<Snowflake>,
+    #[serde(default)]
     pub flags: u64,
     pub guild_id: Snowflake,
     pub name: String,
@@ -63,15 +92,31 @@ pub struct GuildVoiceData {
     pub last_pin_timestamp: Option<OffsetDateTime>,
     #[serde(rename = "parent_id")]
     pub category_id: Option<Snowflake>,
+    #[serde(default)]
     pub rate_limit_per_user: u32,
+    #[serde(default)]
     pub bitrate: u32,
+    #[serde(default)]
     pub user_limit: u32,
     pub rtc_region: Option<String>,
     pub position: u32,
     pub permission_overwrites: Vec<PermissionOverwrite>,
+    #[serde(default)]
     pub nsfw: bool,
 }
 
+/// The channel of a student hub, listing the guilds in it.
+#[derive(Deserialize, Debug)]
+pub struct GuildDirectoryData {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    pub name: String,
+    #[serde(rename = "parent_id")]
+    pub category_id: Option<Snowflake>,
+    #[serde(default)]
+    pub position: u32,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct GroupDmData {
     pub id: Snowflake,
@@ -88,6 +133,7 @@ pub struct GroupDmData {
 #[derive(Deserialize, Debug)]
 pub struct GuildCategoryData {
     pub id: Snowflake,
+    #[serde(default)]
     pub flags: u64,
     pub guild_id: Snowflake,
     pub name: String,
@@ -98,7 +144,8 @@ pub struct GuildCategoryData {
 #[derive(Deserialize, Debug)]
 pub struct GuildAnnouncementData {
     pub id: Snowflake,
-    pub last_message_id: Snowflake,
+    pub last_message_id: Option<Snowflake>,
+    #[serde(default)]
     pub flags: u64,
     #[serde(default, with = "time::serde::iso8601::option")]
     pub last_pin_timestamp: Option<OffsetDateTime>,
@@ -106,10 +153,12 @@ pub struct GuildAnnouncementData {
     pub name: String,
     #[serde(rename = "parent_id")]
     pub category_id: Option<Snowflake>,
+    #[serde(default)]
     pub rate_limit_per_user: u32,
     pub topic: Option<String>,
     pub position: u32,
     pub permission_overwrites: Vec<PermissionOverwrite>,
+    #[serde(default)]
     pub nsfw: bool,
     pub theme_color: Option<String>,
 }
@@ -118,17 +167,134 @@ pub struct GuildAnnouncementData {
 pub struct GuildForumData {
     pub id: Snowflake,
     pub last_message_id: Option<Snowflake>,
+    #[serde(default)]
     pub flags: u64,
     pub guild_id: Snowflake,
     pub name: String,
     #[serde(rename = "parent_id")]
-    pub category_id: Snowflake,
+    pub category_id: Option<Snowflake>,
+    #[serde(default)]
     pub rate_limit_per_user: u32,
+    /// Shown as the guidelines of the forum.
     pub topic: Option<String>,
     pub position: u32,
     pub permission_overwrites: Vec<PermissionOverwrite>,
+    #[serde(default)]
     pub nsfw: bool,
     pub available_tags: Vec<GuildForumTag>,
+    pub default_reaction_emoji: Option<DefaultReaction>,
+    /// The slowmode set on new posts.
+    pub default_thread_rate_limit_per_user: Option<u32>,
+    pub default_auto_archive_duration: Option<AutoArchiveDuration>,
+    pub default_sort_order: Option<SortOrderType>,
+    #[serde(default)]
+    pub default_forum_layout: ForumLayoutType,
+}
+
+/// Like a forum channel, but made for posts of images and videos.
+#[derive(Deserialize, Debug)]
+pub struct GuildMediaData {
+    pub id: Snowflake,
+    pub last_message_id: Option<Snowflake>,
+    #[serde(default)]
+    pub flags: u64,
+    pub guild_id: Snowflake,
+    pub name: String,
+    #[serde(rename = "parent_id")]
+    pub category_id: Option<Snowflake>,
+    #[serde(default)]
+    pub rate_limit_per_user: u32,
+    /// Shown as the guidelines of the channel.
+    pub topic: Option<String>,
+    pub position: u32,
+    pub permission_overwrites: Vec<PermissionOverwrite>,
+    #[serde(default)]
+    pub nsfw: bool,
+    pub available_tags: Vec<GuildForumTag>,
+    pub default_reaction_emoji: Option<DefaultReaction>,
+    /// The slowmode set on new posts.
+    pub default_thread_rate_limit_per_user: Option<u32>,
+    pub default_auto_archive_duration: Option<AutoArchiveDuration>,
+    pub default_sort_order: Option<SortOrderType>,
+}
+
+/// How long a thread stays active without new messages before it is archived.
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum AutoArchiveDuration {
+    OneHour = 60,
+    OneDay = 1440,
+    ThreeDays = 4320,
+    OneWeek = 10080,
+}
+
+// https://discord.com/developers/docs/resources/channel#thread-metadata-object
+#[derive(Deserialize, Debug, Clone)]
+pub struct ThreadMetadata {
+    pub archived: bool,
+    pub auto_archive_duration: AutoArchiveDuration,
+    /// When the archive status was last changed.
+    #[serde(with = "time::serde::iso8601")]
+    pub archive_timestamp: OffsetDateTime,
+    /// Locked threads can only be unarchived by users with `MANAGE_THREADS`.
+    pub locked: bool,
+    /// Whether non moderators can add other non moderators to a private thread.
+    pub invitable: Option<bool>,
+    /// Only present for threads created after 2022-01-09.
+    #[serde(default, with = "time::serde::iso8601::option")]
+    pub create_timestamp: Option<OffsetDateTime>,
+}
+
+/// A user that joined a thread.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ThreadMember {
+    /// The thread's id, missing from the members in a `GuildCreate` event.
+    pub id: Option<Snowflake>,
+    /// Missing from the members in a `GuildCreate` event.
+    pub user_id: Option<Snowflake>,
+    #[serde(with = "time::serde::iso8601")]
+    pub join_timestamp: OffsetDateTime,
+    pub flags: u64,
+}
+
+// Shared by announcement, public and private threads.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ThreadData {
+    pub id: Snowflake,
+    pub guild_id: Option<Snowflake>,
+    /// The channel the thread was created in.
+    pub parent_id: Option<Snowflake>,
+    /// The user that started the thread.
+    pub owner_id: Option<Snowflake>,
+    pub name: String,
+    pub last_message_id: Option<Snowflake>,
+    #[serde(default)]
+    pub flags: u64,
+    #[serde(default, with = "time::serde::iso8601::option")]
+    pub last_pin_timestamp: Option<OffsetDateTime>,
+    pub rate_limit_per_user: Option<u32>,
+    /// Doesn't count the starter message or deleted messages.
+    pub message_count: Option<u32>,
+    /// Stops counting at 50.
+    pub member_count: Option<u32>,
+    /// Unlike `message_count`, this never goes down when messages are deleted.
+    pub total_message_sent: Option<u32>,
+    pub thread_metadata: ThreadMetadata,
+    /// The current user's thread member, if they have joined the thread.
+    pub member: Option<ThreadMember>,
+    /// The ids of the forum tags applied to a forum post.
+    #[serde(default)]
+    pub applied_tags: Vec<Snowflake>,
+}
+
+/// A page of threads, along with the current user's memberships in them.
+/// Active threads aren't paginated, so `has_more` is always false for them.
+#[derive(Deserialize, Debug)]
+pub struct ThreadList {
+    pub threads: Vec<Channel>,
+    pub members: Vec<ThreadMember>,
+    #[serde(default)]
+    pub has_more: bool,
 }
 
 #[derive(FromPrimitive)]
@@ -165,19 +331,19 @@ pub enum Channel {
     // type 5
     GuildAnnouncement(GuildAnnouncementData),
     // type 10
-    AnnouncementThread,
+    AnnouncementThread(ThreadData),
     // type 11
-    PublicThread,
+    PublicThread(ThreadData),
     // type 12
-    PrivateThread,
+    PrivateThread(ThreadData),
     // type 13
-    GuildStageVoice,
+    GuildStageVoice(GuildVoiceData),
     // type 14
-    GuildDirectory,
+    GuildDirectory(GuildDirectoryData),
     // type 15
     GuildForum(GuildForumData),
     // type 16
-    GuildMedia,
+    GuildMedia(GuildMediaData),
 }
 
 #[derive(Deserialize)]
@@ -203,12 +369,12 @@ impl<'de> serde::Deserialize<'de> for Channel {
                 ChannelType::GuildCategory => Channel::GuildCategory(GuildCategoryData::deserialize(value).map_err(D::Error::custom)?),
                 ChannelType::GuildAnnouncement => Channel::GuildAnnouncement(GuildAnnouncementData::deserialize(value).map_err(D::Error::custom)?),
                 ChannelType::GuildForum => Channel::GuildForum(GuildForumData::deserialize(value).map_err(D::Error::custom)?),
-                ChannelType::AnnouncementThread => Channel::AnnouncementThread,
-                ChannelType::PublicThread => Channel::PublicThread,
-                ChannelType::PrivateThread => Channel::PrivateThread,
-                ChannelType::GuildStageVoice => Channel::GuildStageVoice,
-                ChannelType::GuildDirectory => Channel::GuildDirectory,
-                ChannelType::GuildMedia => Channel::GuildMedia,
+                ChannelType::AnnouncementThread => Channel::AnnouncementThread(ThreadData::deserialize(value).map_err(D::Error::custom)?),
+                ChannelType::PublicThread => Channel::PublicThread(ThreadData::deserialize(value).map_err(D::Error::custom)?),
+                ChannelType::PrivateThread => Channel::PrivateThread(ThreadData::deserialize(value).map_err(D::Error::custom)?),
+                ChannelType::GuildStageVoice => Channel::GuildStageVoice(GuildVoiceData::deserialize(value).map_err(D::Error::custom)?),
+                ChannelType::GuildDirectory => Channel::GuildDirectory(GuildDirectoryData::deserialize(value).map_err(D::Error::custom)?),
+                ChannelType::GuildMedia => Channel::GuildMedia(GuildMediaData::deserialize(value).map_err(D::Error::custom)?),
             }
         )
     }
@@ -220,16 +386,211 @@ impl ID for Channel {
             Channel::Dm(dm_data) => &dm_data.id,
             Channel::GroupDm(group_dm_data) => &group_dm_data.id,
             Channel::GuildText(guild_text_data) => &guild_text_data.id,
-            Channel::GuildVoice(guild_voice_data) => &guild_voice_data.id,
+            Channel::GuildVoice(guild_voice_data)
+            | Channel::GuildStageVoice(guild_voice_data) => &guild_voice_data.id,
             Channel::GuildCategory(guild_category_data) => &guild_category_data.id,
             Channel::GuildAnnouncement(guild_announcement_data) => &guild_announcement_data.id,
             Channel::GuildForum(guild_forum_data) => &guild_forum_data.id,
-            Channel::AnnouncementThread => todo!(),
-            Channel::PublicThread => todo!(),
-            Channel::PrivateThread => todo!(),
-            Channel::GuildStageVoice => todo!(),
-            Channel::GuildDirectory => todo!(),
-            Channel::GuildMedia => todo!(),
+            Channel::AnnouncementThread(thread_data)
+            | Channel::PublicThread(thread_data)
+            | Channel::PrivateThread(thread_data) => &thread_data.id,
+            Channel::GuildDirectory(guild_directory_data) => &guild_directory_data.id,
+            Channel::GuildMedia(guild_media_data) => &guild_media_data.id,
         }
     }
-}
\ No newline at end of file
+}
+impl ThreadList {
+    /// The threads, with the current user's membership set on those they joined.
+    pub fn into_threads(self) -> Vec<ThreadData> {
+        let mut members = self.members;
+        self.threads.into_iter()
+            .filter_map(Channel::into_thread)
+            .map(|mut thread| {
+                if let Some(i) = members.iter().position(|member| member.id.as_ref() == Some(&thread.id)) {
+                    thread.member = Some(members.swap_remove(i));
+                }
+                thread
+            })
+            .collect()
+    }
+}
+
+impl Channel {
+    /// The thread's data, if the channel is a thread.
+    pub fn into_thread(self) -> Option<ThreadData> {
+        match self {
+            Channel::AnnouncementThread(thread_data)
+            | Channel::PublicThread(thread_data)
+            | Channel::PrivateThread(thread_data) => Some(thread_data),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The examples from https://discord.com/developers/docs/resources/channel,
+    // and payloads shaped after the field tables for the types without one.
+
+    const VOICE: &str = r#"{
+        "id": "155101607195836416",
+        "last_message_id": "174629835082649376",
+        "type": 2,
+        "name": "ROCKET CHEESE",
+        "nsfw": false,
+        "position": 5,
+        "guild_id": "41771983423143937",
+        "user_limit": 0,
+        "bitrate": 64000,
+        "parent_id": null,
+        "permission_overwrites": [],
+        "rtc_region": null
+    }"#;
+
+    const STAGE: &str = r#"{
+        "id": "1004553210497028187",
+        "type": 13,
+        "guild_id": "41771983423143937",
+        "name": "Town Hall",
+        "position": 2,
+        "parent_id": "399942396007890945",
+        "permission_overwrites": [
+            {"id": "41771983423143937", "type": 0, "allow": "0", "deny": "4294967296"}
+        ],
+        "rtc_region": "us-east",
+        "topic": null
+    }"#;
+
+    const DIRECTORY: &str = r#"{
+        "id": "1004553210497028188",
+        "type": 14,
+        "guild_id": "41771983423143937",
+        "name": "directory",
+        "parent_id": null
+    }"#;
+
+    const THREAD: &str = r#"{
+        "id": "41771983423143937",
+        "guild_id": "41771983423143937",
+        "parent_id": "41771983423143937",
+        "owner_id": "41771983423143937",
+        "name": "don't buy dota-2",
+        "type": 11,
+        "last_message_id": "155117677105512449",
+        "message_count": 1,
+        "member_count": 5,
+        "rate_limit_per_user": 2,
+        "thread_metadata": {
+            "archived": false,
+            "auto_archive_duration": 1440,
+            "archive_timestamp": "2021-04-12T23:40:39.855793+00:00",
+            "locked": false
+        },
+        "total_message_sent": 1
+    }"#;
+
+    const FORUM: &str = r#"{
+        "id": "1004553210497028189",
+        "type": 15,
+        "guild_id": "41771983423143937",
+        "name": "help",
+        "position": 3,
+        "parent_id": null,
+        "permission_overwrites": [],
+        "flags": 16,
+        "topic": "Ask away",
+        "last_message_id": null,
+        "available_tags": [
+            {"id": "1004553210497028190", "name": "solved", "moderated": true, "emoji_id": null, "emoji_name": "✅"}
+        ],
+        "default_reaction_emoji": {"emoji_id": null, "emoji_name": "👍"},
+        "default_thread_rate_limit_per_user": 0,
+        "default_auto_archive_duration": 10080,
+        "default_sort_order": null,
+        "default_forum_layout": 1
+    }"#;
+
+    const MEDIA: &str = r#"{
+        "id": "1004553210497028191",
+        "type": 16,
+        "guild_id": "41771983423143937",
+        "name": "clips",
+        "position": 4,
+        "parent_id": null,
+        "permission_overwrites": [],
+        "nsfw": true,
+        "rate_limit_per_user": 30,
+        "topic": null,
+        "available_tags": [],
+        "default_reaction_emoji": null,
+        "default_sort_order": 1
+    }"#;
+
+    fn channel(json: &str) -> Channel {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn voice_channels_default_omitted_fields() {
+        let Channel::GuildVoice(voice) = channel(VOICE) else { panic!("not a voice channel") };
+        assert_eq!(voice.bitrate, 64000);
+        assert_eq!(voice.flags, 0);
+        assert_eq!(voice.rate_limit_per_user, 0);
+    }
+
+    #[test]
+    fn stage_channels() {
+        let stage = channel(STAGE);
+        assert_eq!(stage.id(), &Snowflake::new("1004553210497028187"));
+        let Channel::GuildStageVoice(stage) = stage else { panic!("not a stage channel") };
+        assert_eq!(stage.bitrate, 0);
+        assert_eq!(stage.user_limit, 0);
+        assert!(!stage.nsfw);
+        assert_eq!(stage.rtc_region.as_deref(), Some("us-east"));
+        assert_eq!(stage.permission_overwrites[0].deny.bits(), 1 << 32);
+    }
+
+    #[test]
+    fn directory_channels() {
+        let directory = channel(DIRECTORY);
+        assert_eq!(directory.id(), &Snowflake::new("1004553210497028188"));
+        let Channel::GuildDirectory(directory) = directory else { panic!("not a directory channel") };
+        assert_eq!(directory.name, "directory");
+        assert_eq!(directory.position, 0);
+    }
+
+    #[test]
+    fn thread_channels() {
+        let thread = channel(THREAD).into_thread().expect("not a thread");
+        assert_eq!(thread.owner_id, Some(Snowflake::new("41771983423143937")));
+        assert_eq!(thread.member_count, Some(5));
+        assert_eq!(thread.thread_metadata.auto_archive_duration, AutoArchiveDuration::OneDay);
+        assert!(!thread.thread_metadata.archived);
+        assert!(thread.applied_tags.is_empty());
+        assert!(thread.member.is_none());
+    }
+
+    #[test]
+    fn forum_channels() {
+        let Channel::GuildForum(forum) = channel(FORUM) else { panic!("not a forum channel") };
+        assert_eq!(forum.flags, 16);
+        assert_eq!(forum.available_tags.len(), 1);
+        assert!(forum.available_tags[0].moderated);
+        assert_eq!(forum.default_reaction_emoji.unwrap().emoji_name.as_deref(), Some("👍"));
+        assert_eq!(forum.default_auto_archive_duration, Some(AutoArchiveDuration::OneWeek));
+        assert_eq!(forum.default_sort_order, None);
+        assert_eq!(forum.default_forum_layout, ForumLayoutType::ListView);
+    }
+
+    #[test]
+    fn media_channels() {
+        let Channel::GuildMedia(media) = channel(MEDIA) else { panic!("not a media channel") };
+        assert!(media.nsfw);
+        assert_eq!(media.rate_limit_per_user, 30);
+        assert_eq!(media.flags, 0);
+        assert_eq!(media.default_sort_order, Some(SortOrderType::CreationDate));
+        assert!(media.last_message_id.is_none());
+    }
+}
@@ -1,9 +1,12 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use time::OffsetDateTime;
 use crate::model::*;
+use message::{Emoji, Sticker};
+use permissions::Permissions;
 use user::UserData;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildMemberData {
     pub avatar: Option<String>,
     #[serde(default, with = "time::serde::iso8601::option")]
@@ -14,26 +17,385 @@ pub struct GuildMemberData {
     pub joined_at: OffsetDateTime,
     pub mute: bool,
     pub nick: Option<String>,
+    /// Whether the member has yet to pass the guild's membership screening.
+    #[serde(default)]
     pub pending: bool,
     pub premium_since: Option<String>,
-    pub roles: Vec<String>,
+    pub roles: Vec<Snowflake>,
     pub user: Option<UserData>
 }
 
+// https://discord.com/developers/docs/resources/guild#guild-member-object-guild-member-flags
+pub mod guild_member_flag {
+    pub const DID_REJOIN: u64 = 1 << 0;
+    pub const COMPLETED_ONBOARDING: u64 = 1 << 1;
+    pub const BYPASSES_VERIFICATION: u64 = 1 << 2;
+    pub const STARTED_ONBOARDING: u64 = 1 << 3;
+    pub const IS_GUEST: u64 = 1 << 4;
+    pub const STARTED_HOME_ACTIONS: u64 = 1 << 5;
+    pub const COMPLETED_HOME_ACTIONS: u64 = 1 << 6;
+    pub const AUTOMOD_QUARANTINED_USERNAME: u64 = 1 << 7;
+    pub const DM_SETTINGS_UPSELL_ACKNOWLEDGED: u64 = 1 << 9;
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Ban {
+    pub reason: Option<String>,
+    pub user: UserData,
+}
+
+/// The outcome of a bulk ban.
 #[derive(Deserialize, Debug)]
-pub struct ExtraGuildData {
-    
+pub struct BulkBan {
+    pub banned_users: Vec<Snowflake>,
+    /// Users that couldn't be banned, or were already banned.
+    pub failed_users: Vec<Snowflake>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RoleTags {
+    /// Set for the role of a bot.
+    pub bot_id: Option<Snowflake>,
+    /// Set for the role of an integration.
+    pub integration_id: Option<Snowflake>,
+    pub subscription_listing_id: Option<Snowflake>,
+}
+
+/// The colors of a role, a gradient when there is more than one.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoleColors {
+    pub primary_color: u32,
+    pub secondary_color: Option<u32>,
+    /// Only used by the holographic style, which has fixed colors.
+    pub tertiary_color: Option<u32>,
+}
+
+// https://discord.com/developers/docs/topics/permissions#role-object
+#[derive(Deserialize, Debug, Clone)]
+pub struct Role {
+    pub id: Snowflake,
+    pub name: String,
+    /// 0xRRGGBB, 0 if the role has no color.
+    /// Same as the primary color in `colors`.
+    pub color: u32,
+    pub colors: Option<RoleColors>,
+    /// Whether the role's members are shown separately in the member list.
+    pub hoist: bool,
+    /// The role icon's hash.
+    pub icon: Option<String>,
+    pub unicode_emoji: Option<String>,
+    pub position: i32,
+    pub permissions: Permissions,
+    /// Whether the role is managed by an integration.
+    pub managed: bool,
+    pub mentionable: bool,
+    pub tags: Option<RoleTags>,
+    #[serde(default)]
+    pub flags: u64,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum VerificationLevel {
+    None = 0,
+    /// Must have a verified email.
+    Low = 1,
+    /// Must also be registered for longer than 5 minutes.
+    Medium = 2,
+    /// Must also be a member of the guild for longer than 10 minutes.
+    High = 3,
+    /// Must also have a verified phone number.
+    VeryHigh = 4,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DefaultMessageNotificationLevel {
+    AllMessages = 0,
+    OnlyMentions = 1,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ExplicitContentFilterLevel {
+    Disabled = 0,
+    MembersWithoutRoles = 1,
+    AllMembers = 2,
+}
+
+#[derive(Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MfaLevel {
+    None = 0,
+    /// Moderators need 2FA.
+    Elevated = 1,
+}
+
+#[derive(Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum NsfwLevel {
+    Default = 0,
+    Explicit = 1,
+    Safe = 2,
+    AgeRestricted = 3,
+}
+
+/// The boost level of a guild.
+#[derive(Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum PremiumTier {
+    #[default]
+    None = 0,
+    Tier1 = 1,
+    Tier2 = 2,
+    Tier3 = 3,
 }
 
+// https://discord.com/developers/docs/resources/guild#guild-object-system-channel-flags
+pub mod system_channel_flag {
+    pub const SUPPRESS_JOIN_NOTIFICATIONS: u64 = 1 << 0;
+    pub const SUPPRESS_PREMIUM_SUBSCRIPTIONS: u64 = 1 << 1;
+    pub const SUPPRESS_GUILD_REMINDER_NOTIFICATIONS: u64 = 1 << 2;
+    pub const SUPPRESS_JOIN_NOTIFICATION_REPLIES: u64 = 1 << 3;
+    pub const SUPPRESS_ROLE_SUBSCRIPTION_PURCHASE_NOTIFICATIONS: u64 = 1 << 4;
+    pub const SUPPRESS_ROLE_SUBSCRIPTION_PURCHASE_NOTIFICATION_REPLIES: u64 = 1 << 5;
+}
+
+/// A guild, either in full from `DiscordClient::guild` or partially from `DiscordClient::guilds`.
+/// The partial guilds only have the id, name, icon, banner, owner, permissions,
+/// features and approximate counts, the rest is left empty.
+// https://discord.com/developers/docs/resources/guild#guild-object
 #[derive(Deserialize, Debug)]
 pub struct Guild {
     id: Snowflake,
     name: String,
     icon: Option<String>,
     banner: Option<String>,
+    splash: Option<String>,
+    discovery_splash: Option<String>,
+    description: Option<String>,
     #[serde(rename = "owner")]
-    is_owner: bool,
-    extra_data: Option<ExtraGuildData>
+    is_owner: Option<bool>,
+    owner_id: Option<Snowflake>,
+    /// The current user's permissions, only in partial guilds.
+    permissions: Option<Permissions>,
+    #[serde(default)]
+    features: Vec<String>,
+    verification_level: Option<VerificationLevel>,
+    default_message_notifications: Option<DefaultMessageNotificationLevel>,
+    explicit_content_filter: Option<ExplicitContentFilterLevel>,
+    mfa_level: Option<MfaLevel>,
+    nsfw_level: Option<NsfwLevel>,
+    #[serde(default)]
+    roles: Vec<Role>,
+    #[serde(default)]
+    emojis: Vec<Emoji>,
+    #[serde(default)]
+    stickers: Vec<Sticker>,
+    afk_channel_id: Option<Snowflake>,
+    afk_timeout: Option<u32>,
+    system_channel_id: Option<Snowflake>,
+    #[serde(default)]
+    system_channel_flags: u64,
+    rules_channel_id: Option<Snowflake>,
+    public_updates_channel_id: Option<Snowflake>,
+    #[serde(default)]
+    premium_tier: PremiumTier,
+    premium_subscription_count: Option<u32>,
+    #[serde(default)]
+    premium_progress_bar_enabled: bool,
+    vanity_url_code: Option<String>,
+    preferred_locale: Option<String>,
+    max_members: Option<u32>,
+    approximate_member_count: Option<u32>,
+    approximate_presence_count: Option<u32>,
+}
+
+impl Guild {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The icon's hash.
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// The banner's hash.
+    pub fn banner(&self) -> Option<&str> {
+        self.banner.as_deref()
+    }
+
+    /// The invite splash's hash.
+    pub fn splash(&self) -> Option<&str> {
+        self.splash.as_deref()
+    }
+
+    pub fn discovery_splash(&self) -> Option<&str> {
+        self.discovery_splash.as_deref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Whether the current user owns the guild.
+    /// Known for partial guilds, for full guilds compare `owner_id` to the user's id.
+    pub fn is_owner(&self) -> Option<bool> {
+        self.is_owner
+    }
+
+    /// Only known for full guilds.
+    pub fn owner_id(&self) -> Option<&Snowflake> {
+        self.owner_id.as_ref()
+    }
+
+    /// The current user's permissions in the guild, only known for partial guilds.
+    pub fn permissions(&self) -> Option<Permissions> {
+        self.permissions
+    }
+
+    /// e.g. "COMMUNITY", "VANITY_URL", "ANIMATED_ICON"...
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+
+    pub fn verification_level(&self) -> Option<VerificationLevel> {
+        self.verification_level
+    }
+
+    pub fn default_message_notifications(&self) -> Option<DefaultMessageNotificationLevel> {
+        self.default_message_notifications
+    }
+
+    pub fn explicit_content_filter(&self) -> Option<ExplicitContentFilterLevel> {
+        self.explicit_content_filter
+    }
+
+    pub fn mfa_level(&self) -> Option<MfaLevel> {
+        self.mfa_level
+    }
+
+    pub fn nsfw_level(&self) -> Option<NsfwLevel> {
+        self.nsfw_level
+    }
+
+    pub fn roles(&self) -> &[Role] {
+        &self.roles
+    }
+
+    pub fn role(&self, role_id: &Snowflake) -> Option<&Role> {
+        self.roles.iter().find(|role| &role.id == role_id)
+    }
+
+    /// The @everyone role, which shares the guild's id.
+    pub fn everyone_role(&self) -> Option<&Role> {
+        self.role(&self.id)
+    }
+
+    pub fn emojis(&self) -> &[Emoji] {
+        &self.emojis
+    }
+
+    pub fn stickers(&self) -> &[Sticker] {
+        &self.stickers
+    }
+
+    pub fn afk_channel_id(&self) -> Option<&Snowflake> {
+        self.afk_channel_id.as_ref()
+    }
+
+    /// How long until inactive users are moved to the afk channel, in seconds.
+    pub fn afk_timeout(&self) -> Option<u32> {
+        self.afk_timeout
+    }
+
+    /// Where join and boost messages are sent.
+    pub fn system_channel_id(&self) -> Option<&Snowflake> {
+        self.system_channel_id.as_ref()
+    }
+
+    /// See `system_channel_flag`.
+    pub fn system_channel_flags(&self) -> u64 {
+        self.system_channel_flags
+    }
+
+    pub fn rules_channel_id(&self) -> Option<&Snowflake> {
+        self.rules_channel_id.as_ref()
+    }
+
+    /// Where community guilds receive notices from discord.
+    pub fn public_updates_channel_id(&self) -> Option<&Snowflake> {
+        self.public_updates_channel_id.as_ref()
+    }
+
+    pub fn premium_tier(&self) -> PremiumTier {
+        self.premium_tier
+    }
+
+    /// The number of boosts.
+    pub fn premium_subscription_count(&self) -> Option<u32> {
+        self.premium_subscription_count
+    }
+
+    pub fn premium_progress_bar_enabled(&self) -> bool {
+        self.premium_progress_bar_enabled
+    }
+
+    pub fn vanity_url_code(&self) -> Option<&str> {
+        self.vanity_url_code.as_deref()
+    }
+
+    pub fn preferred_locale(&self) -> Option<&str> {
+        self.preferred_locale.as_deref()
+    }
+
+    pub fn max_members(&self) -> Option<u32> {
+        self.max_members
+    }
+
+    /// Only known when the guild was fetched with counts.
+    pub fn approximate_member_count(&self) -> Option<u32> {
+        self.approximate_member_count
+    }
+
+    /// Only known when the guild was fetched with counts.
+    pub fn approximate_presence_count(&self) -> Option<u32> {
+        self.approximate_presence_count
+    }
+}
+
+impl ID for Role {
+    fn id(&self) -> &Snowflake {
+        &self.id
+    }
+}
+
+impl ID for Guild {
+    fn id(&self) -> &Snowflake {
+        &self.id
+    }
+}
+
+/// What can be seen of a discoverable guild without joining it.
+#[derive(Deserialize, Debug)]
+pub struct GuildPreview {
+    pub id: Snowflake,
+    pub name: String,
+    pub icon: Option<String>,
+    pub splash: Option<String>,
+    pub discovery_splash: Option<String>,
+    pub emojis: Vec<Emoji>,
+    pub features: Vec<String>,
+    pub approximate_member_count: u32,
+    pub approximate_presence_count: u32,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub stickers: Vec<Sticker>,
 }
 
 pub mod interaction {
@@ -64,9 +426,20 @@ pub mod interaction {
 }
 
 pub mod audit_log {
+    use num::FromPrimitive;
+    use num_derive::FromPrimitive;
+    use serde::de::{DeserializeOwned, Error};
     use serde::Deserialize;
-    
-    #[derive(Deserialize, Debug)]
+    use serde_json::Value;
+    use time::OffsetDateTime;
+    use crate::model::channel::{Channel, ThreadData};
+    use crate::model::permissions::{PermissionOverwrite, Permissions};
+    use crate::model::user::UserData;
+    use crate::model::webhook::Webhook;
+    use crate::model::{Snowflake, ID};
+
+    #[derive(FromPrimitive, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(u16)]
     pub enum EventType {
         /// Server settings were updated
         GuildUpdate = 1,
@@ -194,5 +567,260 @@ pub mod audit_log {
         HomeSettingsCreate = 190,
         /// Guild Server Guide was updated
         HomeSettingsUpdate = 191,
+        /// An action this library doesn't know about yet
+        Unknown = u16::MAX,
+    }
+
+    impl<'de> Deserialize<'de> for EventType {
+        fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let action_type = u16::deserialize(d)?;
+            Ok(EventType::from_u16(action_type).unwrap_or(EventType::Unknown))
+        }
+    }
+
+    /// A role added to or removed from a member.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct PartialRole {
+        pub id: Snowflake,
+        pub name: String,
+    }
+
+    /// A change made by an audit logged action.
+    /// The values are None when the field was unset before or after the change.
+    // https://discord.com/developers/docs/resources/audit-log#audit-log-change-object
+    #[derive(Debug, Clone)]
+    pub enum AuditLogChange {
+        Name { old: Option<String>, new: Option<String> },
+        Nick { old: Option<String>, new: Option<String> },
+        Topic { old: Option<String>, new: Option<String> },
+        Description { old: Option<String>, new: Option<String> },
+        Permissions { old: Option<Permissions>, new: Option<Permissions> },
+        Allow { old: Option<Permissions>, new: Option<Permissions> },
+        Deny { old: Option<Permissions>, new: Option<Permissions> },
+        PermissionOverwrites { old: Option<Vec<PermissionOverwrite>>, new: Option<Vec<PermissionOverwrite>> },
+        Color { old: Option<u32>, new: Option<u32> },
+        Hoist { old: Option<bool>, new: Option<bool> },
+        Mentionable { old: Option<bool>, new: Option<bool> },
+        Nsfw { old: Option<bool>, new: Option<bool> },
+        Mute { old: Option<bool>, new: Option<bool> },
+        Deaf { old: Option<bool>, new: Option<bool> },
+        Position { old: Option<i32>, new: Option<i32> },
+        RateLimitPerUser { old: Option<u32>, new: Option<u32> },
+        OwnerId { old: Option<Snowflake>, new: Option<Snowflake> },
+        ChannelId { old: Option<Snowflake>, new: Option<Snowflake> },
+        CommunicationDisabledUntil { old: Option<OffsetDateTime>, new: Option<OffsetDateTime> },
+        /// Roles added to a member.
+        RolesAdded(Vec<PartialRole>),
+        /// Roles removed from a member.
+        RolesRemoved(Vec<PartialRole>),
+        /// Any other change, the values are left as json.
+        Other { key: String, old: Option<Value>, new: Option<Value> },
+    }
+
+    impl AuditLogChange {
+        /// The name of the changed field, as discord calls it.
+        pub fn key(&self) -> &str {
+            match self {
+                AuditLogChange::Name { .. } => "name",
+                AuditLogChange::Nick { .. } => "nick",
+                AuditLogChange::Topic { .. } => "topic",
+                AuditLogChange::Description { .. } => "description",
+                AuditLogChange::Permissions { .. } => "permissions",
+                AuditLogChange::Allow { .. } => "allow",
+                AuditLogChange::Deny { .. } => "deny",
+                AuditLogChange::PermissionOverwrites { .. } => "permission_overwrites",
+                AuditLogChange::Color { .. } => "color",
+                AuditLogChange::Hoist { .. } => "hoist",
+                AuditLogChange::Mentionable { .. } => "mentionable",
+                AuditLogChange::Nsfw { .. } => "nsfw",
+                AuditLogChange::Mute { .. } => "mute",
+                AuditLogChange::Deaf { .. } => "deaf",
+                AuditLogChange::Position { .. } => "position",
+                AuditLogChange::RateLimitPerUser { .. } => "rate_limit_per_user",
+                AuditLogChange::OwnerId { .. } => "owner_id",
+                AuditLogChange::ChannelId { .. } => "channel_id",
+                AuditLogChange::CommunicationDisabledUntil { .. } => "communication_disabled_until",
+                AuditLogChange::RolesAdded(_) => "$add",
+                AuditLogChange::RolesRemoved(_) => "$remove",
+                AuditLogChange::Other { key, .. } => key,
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct RawAuditLogChange {
+        key: String,
+        old_value: Option<Value>,
+        new_value: Option<Value>,
+    }
+
+    fn typed<T: DeserializeOwned, E: Error>(value: Option<Value>) -> Result<Option<T>, E> {
+        match value {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => serde_json::from_value(value).map(Some).map_err(E::custom),
+        }
+    }
+
+    fn timestamp<E: Error>(value: Option<Value>) -> Result<Option<OffsetDateTime>, E> {
+        typed::<String, E>(value)?
+            .map(|timestamp| OffsetDateTime::parse(&timestamp, &time::format_description::well_known::Iso8601::DEFAULT))
+            .transpose()
+            .map_err(E::custom)
+    }
+
+    impl<'de> Deserialize<'de> for AuditLogChange {
+        fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let RawAuditLogChange { key, old_value: old, new_value: new } = RawAuditLogChange::deserialize(d)?;
+
+            Ok(
+                match key.as_str() {
+                    "name" => AuditLogChange::Name { old: typed(old)?, new: typed(new)? },
+                    "nick" => AuditLogChange::Nick { old: typed(old)?, new: typed(new)? },
+                    "topic" => AuditLogChange::Topic { old: typed(old)?, new: typed(new)? },
+                    "description" => AuditLogChange::Description { old: typed(old)?, new: typed(new)? },
+                    "permissions" => AuditLogChange::Permissions { old: typed(old)?, new: typed(new)? },
+                    "allow" => AuditLogChange::Allow { old: typed(old)?, new: typed(new)? },
+                    "deny" => AuditLogChange::Deny { old: typed(old)?, new: typed(new)? },
+                    "permission_overwrites" => AuditLogChange::PermissionOverwrites { old: typed(old)?, new: typed(new)? },
+                    "color" => AuditLogChange::Color { old: typed(old)?, new: typed(new)? },
+                    "hoist" => AuditLogChange::Hoist { old: typed(old)?, new: typed(new)? },
+                    "mentionable" => AuditLogChange::Mentionable { old: typed(old)?, new: typed(new)? },
+                    "nsfw" => AuditLogChange::Nsfw { old: typed(old)?, new: typed(new)? },
+                    "mute" => AuditLogChange::Mute { old: typed(old)?, new: typed(new)? },
+                    "deaf" => AuditLogChange::Deaf { old: typed(old)?, new: typed(new)? },
+                    "position" => AuditLogChange::Position { old: typed(old)?, new: typed(new)? },
+                    "rate_limit_per_user" => AuditLogChange::RateLimitPerUser { old: typed(old)?, new: typed(new)? },
+                    "owner_id" => AuditLogChange::OwnerId { old: typed(old)?, new: typed(new)? },
+                    "channel_id" => AuditLogChange::ChannelId { old: typed(old)?, new: typed(new)? },
+                    "communication_disabled_until" => AuditLogChange::CommunicationDisabledUntil {
+                        old: timestamp(old)?,
+                        new: timestamp(new)?,
+                    },
+                    "$add" => AuditLogChange::RolesAdded(typed(new)?.unwrap_or_default()),
+                    "$remove" => AuditLogChange::RolesRemoved(typed(new)?.unwrap_or_default()),
+                    _ => AuditLogChange::Other { key, old, new },
+                }
+            )
+        }
+    }
+
+    /// Extra information about some actions.
+    /// Which fields are set depends on the action type.
+    // https://discord.com/developers/docs/resources/audit-log#audit-log-entry-object-optional-audit-entry-info
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct AuditLogEntryOptions {
+        pub application_id: Option<Snowflake>,
+        pub auto_moderation_rule_name: Option<String>,
+        pub auto_moderation_rule_trigger_type: Option<String>,
+        pub channel_id: Option<Snowflake>,
+        /// The number of entities affected.
+        pub count: Option<String>,
+        /// How many days of inactivity members were pruned after.
+        pub delete_member_days: Option<String>,
+        /// The id of the overwritten role or member.
+        pub id: Option<Snowflake>,
+        pub members_removed: Option<String>,
+        pub message_id: Option<Snowflake>,
+        pub role_name: Option<String>,
+        /// "0" for a role overwrite, "1" for a member overwrite.
+        #[serde(rename = "type")]
+        pub overwrite_type: Option<String>,
+        pub integration_type: Option<String>,
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct IntegrationAccount {
+        pub id: String,
+        pub name: String,
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct PartialIntegration {
+        pub id: Snowflake,
+        pub name: String,
+        /// "twitch", "youtube", "discord" or "guild_subscription".
+        #[serde(rename = "type")]
+        pub integration_type: String,
+        pub account: IntegrationAccount,
+        pub application_id: Option<Snowflake>,
+    }
+
+    /// What an action was done to, when it was found in the audit log page.
+    #[derive(Debug, Clone)]
+    pub enum AuditLogTarget {
+        User(UserData),
+        Webhook(Webhook),
+        Thread(ThreadData),
+        Integration(PartialIntegration),
+    }
+
+    // https://discord.com/developers/docs/resources/audit-log#audit-log-entry-object
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct AuditLogEntry {
+        pub id: Snowflake,
+        pub action_type: EventType,
+        /// The user or app that made the changes.
+        pub user_id: Option<Snowflake>,
+        /// The id of what the action was done to, its type depends on the action.
+        pub target_id: Option<Snowflake>,
+        #[serde(default)]
+        pub changes: Vec<AuditLogChange>,
+        pub options: Option<AuditLogEntryOptions>,
+        /// Given with the `X-Audit-Log-Reason` header.
+        pub reason: Option<String>,
+        /// The user that made the changes, resolved from the audit log page.
+        #[serde(skip)]
+        pub user: Option<UserData>,
+        /// What the action was done to, resolved from the audit log page.
+        /// None for entities the audit log doesn't include, like channels and roles.
+        #[serde(skip)]
+        pub target: Option<AuditLogTarget>,
+    }
+
+    impl ID for AuditLogEntry {
+        fn id(&self) -> &Snowflake {
+            &self.id
+        }
+    }
+
+    /// A page of a guild's audit log, along with the entities its entries refer to.
+    // https://discord.com/developers/docs/resources/audit-log#audit-log-object
+    #[derive(Deserialize, Debug)]
+    pub struct AuditLog {
+        pub audit_log_entries: Vec<AuditLogEntry>,
+        #[serde(default)]
+        pub users: Vec<UserData>,
+        #[serde(default)]
+        pub webhooks: Vec<Webhook>,
+        #[serde(default)]
+        pub threads: Vec<Channel>,
+        #[serde(default)]
+        pub integrations: Vec<PartialIntegration>,
+    }
+
+    impl AuditLog {
+        /// The entries, with the users and targets they refer to filled in.
+        pub fn into_entries(self) -> Vec<AuditLogEntry> {
+            let threads: Vec<ThreadData> = self.threads.into_iter()
+                .filter_map(Channel::into_thread)
+                .collect();
+            let users = self.users;
+            let webhooks = self.webhooks;
+            let integrations = self.integrations;
+
+            let find_user = |id: &Snowflake| users.iter().find(|user| &user.id == id).cloned();
+            self.audit_log_entries.into_iter()
+                .map(|mut entry| {
+                    entry.user = entry.user_id.as_ref().and_then(find_user);
+                    entry.target = entry.target_id.as_ref().and_then(|id| {
+                        find_user(id).map(AuditLogTarget::User)
+                            .or_else(|| webhooks.iter().find(|webhook| &webhook.id == id).cloned().map(AuditLogTarget::Webhook))
+                            .or_else(|| threads.iter().find(|thread| &thread.id == id).cloned().map(AuditLogTarget::Thread))
+                            .or_else(|| integrations.iter().find(|integration| &integration.id == id).cloned().map(AuditLogTarget::Integration))
+                    });
+                    entry
+                })
+                .collect()
+        }
     }
 }
\ No newline at end of file
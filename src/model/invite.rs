@@ -0,0 +1,81 @@
+use serde::Deserialize;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use time::OffsetDateTime;
+use super::guild::Guild;
+use super::user::UserData;
+use super::Snowflake;
+
+#[derive(Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum InviteType {
+    Guild = 0,
+    GroupDm = 1,
+    Friend = 2,
+}
+
+/// What a voice channel invite opens once joined.
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum InviteTargetType {
+    /// A user's stream.
+    Stream = 1,
+    /// An embedded application (activity).
+    EmbeddedApplication = 2,
+}
+
+/// The channel an invite leads to.
+#[derive(Deserialize, Debug, Clone)]
+pub struct InviteChannel {
+    pub id: Snowflake,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub channel_type: u8,
+}
+
+// https://discord.com/developers/docs/resources/invite#invite-object
+#[derive(Deserialize, Debug)]
+pub struct Invite {
+    #[serde(rename = "type")]
+    pub invite_type: Option<InviteType>,
+    pub code: String,
+    /// A partial guild, as it's seen before joining.
+    pub guild: Option<Guild>,
+    pub channel: Option<InviteChannel>,
+    pub inviter: Option<UserData>,
+    pub target_type: Option<InviteTargetType>,
+    pub target_user: Option<UserData>,
+    /// Only present when resolved with counts.
+    pub approximate_member_count: Option<u32>,
+    /// Only present when resolved with counts.
+    pub approximate_presence_count: Option<u32>,
+    /// None if the invite never expires.
+    #[serde(default, with = "time::serde::iso8601::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    /// How many times the invite was used.
+    /// This and the fields below are only present for created and listed invites.
+    pub uses: Option<u32>,
+    /// 0 for unlimited uses.
+    pub max_uses: Option<u32>,
+    /// In seconds, 0 if the invite never expires.
+    pub max_age: Option<u32>,
+    /// Whether members that joined with the invite are kicked once they disconnect,
+    /// unless they were given a role.
+    pub temporary: Option<bool>,
+    #[serde(default, with = "time::serde::iso8601::option")]
+    pub created_at: Option<OffsetDateTime>,
+}
+
+impl Invite {
+    pub fn url(&self) -> String {
+        format!("https://discord.gg/{}", self.code)
+    }
+}
+
+/// Gets the code out of an invite url like `https://discord.gg/code`,
+/// codes are returned as they are.
+pub fn invite_code(code_or_url: &str) -> &str {
+    let code = code_or_url.trim();
+    let code = code.split(['?', '#']).next().unwrap_or(code);
+    let code = code.trim_end_matches('/');
+    code.rsplit('/').next().unwrap_or(code)
+}
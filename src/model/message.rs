@@ -1,18 +1,159 @@
-use arboard::Clipboard;
+use std::fmt::Display;
 use num::FromPrimitive;
 use num_derive::FromPrimitive;
 use serde::{de::Error, Deserialize};
+use serde_repr::Deserialize_repr;
 use serde_json::Value;
-use time::{Duration, OffsetDateTime};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use time::OffsetDateTime;
 use crate::model::{guild::{GuildMemberData, interaction::*}, user::UserData, voice::PrivateCallData};
 
 use super::{Snowflake, ID};
 
-#[derive(Deserialize, Debug)]
+/// A unicode emoji when `id` is None, otherwise a custom emoji.
+#[derive(Deserialize, Debug, Clone)]
 pub struct Emoji {
-    animated: bool,
-    id: Snowflake,
-    name: String
+    pub id: Option<Snowflake>,
+    /// The emoji itself for unicode emojis.
+    /// None for custom emojis that were deleted.
+    pub name: Option<String>,
+    #[serde(default)]
+    pub animated: bool,
+    /// The roles allowed to use a guild emoji, everyone can if empty.
+    #[serde(default)]
+    pub roles: Vec<Snowflake>,
+    /// The user that uploaded a guild emoji.
+    pub user: Option<UserData>,
+    pub require_colons: Option<bool>,
+    /// Whether the emoji is managed by an integration.
+    pub managed: Option<bool>,
+    /// May be false when the guild loses boosts.
+    pub available: Option<bool>,
+}
+
+#[derive(Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StickerType {
+    /// Part of a pack from discord.
+    Standard = 1,
+    /// Uploaded to a guild.
+    Guild = 2,
+}
+
+#[derive(Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StickerFormatType {
+    Png = 1,
+    Apng = 2,
+    Lottie = 3,
+    Gif = 4,
+}
+
+// https://discord.com/developers/docs/resources/sticker#sticker-object
+#[derive(Deserialize, Debug, Clone)]
+pub struct Sticker {
+    pub id: Snowflake,
+    pub pack_id: Option<Snowflake>,
+    pub name: String,
+    pub description: Option<String>,
+    /// Autocomplete keywords, comma separated.
+    pub tags: String,
+    #[serde(rename = "type")]
+    pub sticker_type: StickerType,
+    pub format_type: StickerFormatType,
+    /// May be false when the guild loses boosts.
+    pub available: Option<bool>,
+    pub guild_id: Option<Snowflake>,
+    /// The user that uploaded a guild sticker.
+    pub user: Option<UserData>,
+    pub sort_value: Option<u32>,
+}
+
+/// An emoji to react with, either unicode or custom.
+/// Convert from a `&str` for unicode emojis (e.g. "🔥") and from an `Emoji` for custom ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReactionEmoji {
+    Unicode(String),
+    Custom { id: Snowflake, name: String },
+}
+
+impl ReactionEmoji {
+    /// How the emoji is written in a route, as `name:id` for custom emojis.
+    pub(crate) fn route_segment(&self) -> String {
+        let emoji = match self {
+            ReactionEmoji::Unicode(emoji) => emoji.clone(),
+            ReactionEmoji::Custom { id, name } => format!("{name}:{id}"),
+        };
+        utf8_percent_encode(&emoji, NON_ALPHANUMERIC).to_string()
+    }
+}
+
+impl Display for ReactionEmoji {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReactionEmoji::Unicode(emoji) => f.write_str(emoji),
+            ReactionEmoji::Custom { id, name } => write!(f, "<:{name}:{id}>"),
+        }
+    }
+}
+
+impl From<&str> for ReactionEmoji {
+    fn from(emoji: &str) -> ReactionEmoji {
+        ReactionEmoji::Unicode(emoji.to_string())
+    }
+}
+
+impl From<String> for ReactionEmoji {
+    fn from(emoji: String) -> ReactionEmoji {
+        ReactionEmoji::Unicode(emoji)
+    }
+}
+
+impl From<&Emoji> for ReactionEmoji {
+    fn from(emoji: &Emoji) -> ReactionEmoji {
+        let name = emoji.name.clone().unwrap_or_default();
+        match &emoji.id {
+            Some(id) => ReactionEmoji::Custom { id: id.clone(), name },
+            None => ReactionEmoji::Unicode(name),
+        }
+    }
+}
+
+impl From<Emoji> for ReactionEmoji {
+    fn from(emoji: Emoji) -> ReactionEmoji {
+        ReactionEmoji::from(&emoji)
+    }
+}
+
+/// Burst reactions are the super reactions nitro users can send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum ReactionType {
+    #[default]
+    Normal = 0,
+    Burst = 1,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReactionCountDetails {
+    pub burst: u64,
+    pub normal: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Reaction {
+    /// Total of normal and burst reactions.
+    pub count: u64,
+    pub count_details: ReactionCountDetails,
+    /// Whether the current user reacted with this emoji.
+    pub me: bool,
+    /// Whether the current user super reacted with this emoji.
+    #[serde(default)]
+    pub me_burst: bool,
+    pub emoji: Emoji,
+    /// Hex colors used for the super reaction.
+    #[serde(default)]
+    pub burst_colors: Vec<String>,
 }
 
 /// Refer to the discord documentation for more info: 
@@ -58,11 +199,54 @@ enum MessageType {
     PurchaseNotification = 44,
 }
 
-// TODO! fill these types
+// https://discord.com/developers/docs/resources/message#message-object-message-flags
+pub mod message_flag {
+    pub const CROSSPOSTED: u64 = 1 << 0;
+    pub const IS_CROSSPOST: u64 = 1 << 1;
+    pub const SUPPRESS_EMBEDS: u64 = 1 << 2;
+    pub const SOURCE_MESSAGE_DELETED: u64 = 1 << 3;
+    pub const URGENT: u64 = 1 << 4;
+    pub const HAS_THREAD: u64 = 1 << 5;
+    pub const EPHEMERAL: u64 = 1 << 6;
+    pub const LOADING: u64 = 1 << 7;
+    pub const FAILED_TO_MENTION_SOME_ROLES_IN_THREAD: u64 = 1 << 8;
+    pub const SUPPRESS_NOTIFICATIONS: u64 = 1 << 12;
+    pub const IS_VOICE_MESSAGE: u64 = 1 << 13;
+    pub const HAS_SNAPSHOT: u64 = 1 << 14;
+    pub const IS_COMPONENTS_V2: u64 = 1 << 15;
+}
+
 #[derive(Deserialize, Debug)]
 pub struct MessageAttachment {
-    id: Snowflake,
+    pub id: Snowflake,
+    pub filename: String,
+    pub title: Option<String>,
+    /// Alt text for the file.
+    pub description: Option<String>,
+    pub content_type: Option<String>,
+    /// Size of the file in bytes.
+    pub size: u64,
+    pub url: String,
+    pub proxy_url: String,
+    /// Only present for images and videos.
+    pub height: Option<u32>,
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub ephemeral: bool,
+    /// Only present for voice messages.
+    pub duration_secs: Option<f64>,
+    /// Base64 encoded waveform, only present for voice messages.
+    pub waveform: Option<String>,
+    pub flags: Option<u64>,
+}
+
+impl MessageAttachment {
+    pub fn is_spoiler(&self) -> bool {
+        self.filename.starts_with("SPOILER_")
+    }
 }
+
+// TODO! fill these types
 #[derive(Deserialize, Debug)]
 pub struct MessageComponent {
     r#type: u64
@@ -72,6 +256,20 @@ pub struct MessageEmbed {
     r#type: String,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct MessagePin {
+    #[serde(with = "time::serde::iso8601")]
+    pub pinned_at: OffsetDateTime,
+    pub message: Message,
+}
+
+/// A page of a channel's pins, newest first.
+#[derive(Deserialize, Debug)]
+pub struct MessagePins {
+    pub items: Vec<MessagePin>,
+    pub has_more: bool,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct GeneralMessageData {
     pub id: Snowflake,
@@ -82,6 +280,8 @@ pub struct GeneralMessageData {
     pub attachments: Vec<MessageAttachment>,
     pub embeds: Vec<MessageEmbed>,
     pub components: Vec<MessageComponent>,
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -162,9 +362,9 @@ pub struct CallMessageData {
 pub enum Message {
     Default(DefaultMessageData),
     Call(CallMessageData),
-    UserJoin(UserJoinData),
+    UserJoin(Box<UserJoinData>),
     Reply(ReplyMessageData),
-    ChatInputCommand(ChatInputCommandData),
+    ChatInputCommand(Box<ChatInputCommandData>),
 
     Unknown(GeneralMessageData)
 }
@@ -202,7 +402,7 @@ impl<'de> serde::Deserialize<'de> for Message {
                 // TODO! for some reason this new value
                 // is missing the guild_id in its json for user
                 let user_join_data = UserJoinData::deserialize(value).map_err(D::Error::custom)?;
-                Message::UserJoin(user_join_data)
+                Message::UserJoin(Box::new(user_join_data))
             },
             // MessageType::GuildBoost => todo!(),
             // MessageType::GuildBoostTier1 => todo!(),
@@ -220,7 +420,7 @@ impl<'de> serde::Deserialize<'de> for Message {
             },
             MessageType::ChatInputCommand => {
                 let chat_input_cmd_data = ChatInputCommandData::deserialize(value).map_err(D::Error::custom)?;
-                Message::ChatInputCommand(chat_input_cmd_data)
+                Message::ChatInputCommand(Box::new(chat_input_cmd_data))
             },
             // MessageType::ThreadStarterMessage => todo!(),
             // MessageType::GuildInviteReminder => todo!(),
@@ -1,11 +1,15 @@
 pub mod channel;
 pub mod guild;
+pub mod invite;
 pub mod message;
 pub mod permissions;
+pub mod scheduled_event;
 pub mod user;
 pub mod voice;
+pub mod webhook;
 
 use std::fmt::{Debug, Display};
+use time::OffsetDateTime;
 
 /// Will be implemented for all discord objects that have a snowflake id.
 pub trait ID {
@@ -39,5 +43,19 @@ impl Snowflake {
             snowflake_str: string.to_string()
         }
     }
+
+    /// Milliseconds since the first second of 2015, the epoch snowflakes count from.
+    pub const DISCORD_EPOCH: u64 = 1420070400000;
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.snowflake_str.parse().ok()
+    }
+
+    /// When the object with this id was created.
+    /// None if the snowflake isn't a valid id.
+    pub fn timestamp(&self) -> Option<OffsetDateTime> {
+        let millis = (self.as_u64()? >> 22) + Self::DISCORD_EPOCH;
+        OffsetDateTime::from_unix_timestamp_nanos(millis as i128 * 1_000_000).ok()
+    }
 }
 
@@ -1,5 +1,8 @@
-use serde::Deserialize;
-use serde_repr::Deserialize_repr;
+use std::fmt::Debug;
+use std::ops::{BitAnd, BitOr};
+use serde::de::Error;
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use super::Snowflake;
 
@@ -55,31 +58,151 @@ pub mod permission_bit_flag {
     pub const SEND_VOICE_MESSAGES: u128 = 1 << 46;
     pub const SEND_POLLS: u128 = 1 << 49;
     pub const USE_EXTERNAL_APPS: u128 = 1 << 50;
+    pub const PIN_MESSAGES: u128 = 1 << 51;
 }
 
 
+/// A set of permissions, made of the flags in `permission_bit_flag`.
+/// Discord sends and receives these as strings of the bit field.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Permissions {
     bit_field: u128
 }
 
 impl Permissions {
+    pub const fn empty() -> Permissions {
+        Permissions { bit_field: 0 }
+    }
+
+    pub const fn from_bits(bit_field: u128) -> Permissions {
+        Permissions { bit_field }
+    }
+
+    pub const fn bits(&self) -> u128 {
+        self.bit_field
+    }
+
     pub fn has_permission(&self, permission_bit_flag: u128) -> bool {
         self.bit_field & permission_bit_flag == permission_bit_flag
     }
+
+    /// Administrators have every permission, whatever their other flags are.
+    pub fn is_admin(&self) -> bool {
+        self.has_permission(permission_bit_flag::ADMINISTRATOR)
+    }
+
+    pub fn with(mut self, permission_bit_flag: u128) -> Permissions {
+        self.insert(permission_bit_flag);
+        self
+    }
+
+    pub fn without(mut self, permission_bit_flag: u128) -> Permissions {
+        self.remove(permission_bit_flag);
+        self
+    }
+
+    pub fn insert(&mut self, permission_bit_flag: u128) {
+        self.bit_field |= permission_bit_flag;
+    }
+
+    pub fn remove(&mut self, permission_bit_flag: u128) {
+        self.bit_field &= !permission_bit_flag;
+    }
 }
 
-#[derive(Deserialize_repr, Debug)]
+impl Debug for Permissions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Permissions({:#b})", self.bit_field)
+    }
+}
+
+impl From<u128> for Permissions {
+    fn from(bit_field: u128) -> Permissions {
+        Permissions { bit_field }
+    }
+}
+
+impl BitOr for Permissions {
+    type Output = Permissions;
+
+    fn bitor(self, rhs: Permissions) -> Permissions {
+        Permissions { bit_field: self.bit_field | rhs.bit_field }
+    }
+}
+
+impl BitAnd for Permissions {
+    type Output = Permissions;
+
+    fn bitand(self, rhs: Permissions) -> Permissions {
+        Permissions { bit_field: self.bit_field & rhs.bit_field }
+    }
+}
+
+impl Serialize for Permissions {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&self.bit_field.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Permissions {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let bit_field = String::deserialize(d)?;
+        bit_field.parse::<u128>()
+            .map(Permissions::from_bits)
+            .map_err(|_| D::Error::custom(format!("invalid permissions: {bit_field}")))
+    }
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum PermissionOverwriteType {
     Role = 0,
     Member = 1,
 }
 
-#[derive(Deserialize, Debug)]
+/// Permissions allowed or denied to a role or member in a channel, on top of their guild permissions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct PermissionOverwrite {
-    id: Snowflake,
+    /// The id of the role or member.
+    pub id: Snowflake,
     #[serde(rename = "type")]
-    r#type: PermissionOverwriteType,
-    allow: String,
-    deny: String
-}
\ No newline at end of file
+    pub r#type: PermissionOverwriteType,
+    pub allow: Permissions,
+    pub deny: Permissions
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+    use permission_bit_flag::*;
+
+    #[test]
+    fn permissions_are_sent_as_strings() {
+        // Above 2^53, where the bits would be lost as a json number read into a double.
+        let permissions = Permissions::from_bits(SEND_MESSAGES | PIN_MESSAGES | (1 << 60));
+        let json = serde_json::to_value(permissions).unwrap();
+        assert_eq!(json, json!("1155173304420534272"));
+        assert_eq!(serde_json::from_value::<Permissions>(json).unwrap(), permissions);
+
+        assert_eq!(PIN_MESSAGES, 2251799813685248);
+        assert!(serde_json::from_value::<Permissions>(json!(2048)).is_err());
+        assert!(serde_json::from_value::<Permissions>(json!("admin")).is_err());
+    }
+
+    #[test]
+    fn overwrites_round_trip() {
+        let json = json!({
+            "id": "41771983423143937",
+            "type": 1,
+            "allow": "2251799813686272",
+            "deny": "0",
+        });
+        let overwrite = serde_json::from_value::<PermissionOverwrite>(json.clone()).unwrap();
+        assert_eq!(overwrite.r#type, PermissionOverwriteType::Member);
+        assert!(overwrite.allow.has_permission(VIEW_CHANNEL | PIN_MESSAGES));
+        assert_eq!(overwrite.deny, Permissions::empty());
+
+        assert_eq!(serde_json::to_value(&overwrite).unwrap(), json);
+    }
+}
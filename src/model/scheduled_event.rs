@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use time::OffsetDateTime;
+use super::guild::GuildMemberData;
+use super::user::UserData;
+use super::{Snowflake, ID};
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum ScheduledEventPrivacyLevel {
+    /// Only members of the guild can see the event.
+    #[default]
+    GuildOnly = 2,
+}
+
+/// Events go from `Scheduled` to `Active` to `Completed`, or from `Scheduled` to `Canceled`.
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ScheduledEventStatus {
+    Scheduled = 1,
+    Active = 2,
+    Completed = 3,
+    Canceled = 4,
+}
+
+/// Where the event takes place.
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ScheduledEventEntityType {
+    StageInstance = 1,
+    Voice = 2,
+    /// Somewhere outside of discord, described by the event's location.
+    External = 3,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ScheduledEventMetadata {
+    /// Only set for external events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RecurrenceFrequency {
+    Yearly = 0,
+    Monthly = 1,
+    Weekly = 2,
+    Daily = 3,
+}
+
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RecurrenceWeekday {
+    Monday = 0,
+    Tuesday = 1,
+    Wednesday = 2,
+    Thursday = 3,
+    Friday = 4,
+    Saturday = 5,
+    Sunday = 6,
+}
+
+/// A weekday within a week of the month, e.g. the second friday.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecurrenceNWeekday {
+    /// The week of the month, 1 to 5.
+    pub n: u8,
+    pub day: RecurrenceWeekday,
+}
+
+// https://discord.com/developers/docs/resources/guild-scheduled-event#guild-scheduled-event-recurrence-rule-object
+/// How an event repeats, a subset of the iCalendar RRULE.
+/// Discord only accepts some combinations of these fields, see the link above.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecurrenceRule {
+    #[serde(with = "time::serde::rfc3339")]
+    pub start: OffsetDateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "time::serde::rfc3339::option")]
+    pub end: Option<OffsetDateTime>,
+    pub frequency: RecurrenceFrequency,
+    /// Repeats every `interval` times the frequency, e.g. every 2 weeks.
+    pub interval: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_weekday: Option<Vec<RecurrenceWeekday>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_n_weekday: Option<Vec<RecurrenceNWeekday>>,
+    /// Months, from 1 (January) to 12.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_month: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_month_day: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_year_day: Option<Vec<u16>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+impl RecurrenceRule {
+    pub fn new(start: OffsetDateTime, frequency: RecurrenceFrequency, interval: u16) -> RecurrenceRule {
+        RecurrenceRule {
+            start,
+            end: None,
+            frequency,
+            interval,
+            by_weekday: None,
+            by_n_weekday: None,
+            by_month: None,
+            by_month_day: None,
+            by_year_day: None,
+            count: None,
+        }
+    }
+}
+
+// https://discord.com/developers/docs/resources/guild-scheduled-event#guild-scheduled-event-object
+#[derive(Deserialize, Debug, Clone)]
+pub struct ScheduledEvent {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    /// None for external events.
+    pub channel_id: Option<Snowflake>,
+    /// None for events created before October 25th, 2021.
+    pub creator_id: Option<Snowflake>,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(with = "time::serde::iso8601")]
+    pub scheduled_start_time: OffsetDateTime,
+    /// Always set for external events.
+    #[serde(default, with = "time::serde::iso8601::option")]
+    pub scheduled_end_time: Option<OffsetDateTime>,
+    pub privacy_level: ScheduledEventPrivacyLevel,
+    pub status: ScheduledEventStatus,
+    pub entity_type: ScheduledEventEntityType,
+    /// The stage instance of stage events.
+    pub entity_id: Option<Snowflake>,
+    pub entity_metadata: Option<ScheduledEventMetadata>,
+    pub creator: Option<UserData>,
+    /// Only present when requested with the user count.
+    pub user_count: Option<u32>,
+    /// The cover image's hash.
+    pub image: Option<String>,
+    pub recurrence_rule: Option<RecurrenceRule>,
+}
+
+impl ScheduledEvent {
+    /// Where an external event takes place.
+    pub fn location(&self) -> Option<&str> {
+        self.entity_metadata.as_ref()?.location.as_deref()
+    }
+}
+
+impl ID for ScheduledEvent {
+    fn id(&self) -> &Snowflake {
+        &self.id
+    }
+}
+
+/// A user subscribed to a scheduled event.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ScheduledEventUser {
+    #[serde(rename = "guild_scheduled_event_id")]
+    pub event_id: Snowflake,
+    pub user: UserData,
+    /// Only present when requested with the members.
+    pub member: Option<GuildMemberData>,
+}
@@ -64,7 +64,7 @@ pub struct GatewayUserData {
     pub bio: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct UserData {
     pub id: Snowflake,
     pub username: String,
@@ -0,0 +1,41 @@
+use serde::Deserialize;
+use serde_repr::Deserialize_repr;
+use super::user::UserData;
+use super::{Snowflake, ID};
+
+#[derive(Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum WebhookType {
+    /// Posts messages to a channel with its token.
+    Incoming = 1,
+    /// Posts the messages of a followed announcement channel.
+    ChannelFollower = 2,
+    /// Used for interactions.
+    Application = 3,
+}
+
+// https://discord.com/developers/docs/resources/webhook#webhook-object
+#[derive(Deserialize, Debug, Clone)]
+pub struct Webhook {
+    pub id: Snowflake,
+    #[serde(rename = "type")]
+    pub webhook_type: WebhookType,
+    pub guild_id: Option<Snowflake>,
+    pub channel_id: Option<Snowflake>,
+    /// The user that created the webhook, missing when fetched with its token.
+    pub user: Option<UserData>,
+    pub name: Option<String>,
+    /// The avatar's hash.
+    pub avatar: Option<String>,
+    /// Only present for incoming webhooks.
+    pub token: Option<String>,
+    pub application_id: Option<Snowflake>,
+    /// The url to execute the webhook with, only present for incoming webhooks.
+    pub url: Option<String>,
+}
+
+impl ID for Webhook {
+    fn id(&self) -> &Snowflake {
+        &self.id
+    }
+}
@@ -0,0 +1,127 @@
+use crate::api::{self, Result};
+use crate::builders::message::EditMessage;
+use crate::builders::webhook::{ExecuteWebhook, ModifyWebhook};
+use crate::client::{ClientConfig, DiscordBuildError};
+use crate::http::retry::RetryPolicy;
+use crate::http::{self, HttpClient};
+use crate::model::message::Message;
+use crate::model::webhook::Webhook;
+use crate::model::Snowflake;
+
+/// Executes a webhook with only its token, without logging into an account.
+#[derive(Clone)]
+pub struct WebhookClient {
+    id: Snowflake,
+    token: String,
+    req_client: HttpClient,
+}
+
+impl std::fmt::Debug for WebhookClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookClient")
+            .field("id", &self.id)
+            .field("token", &"<redacted>")
+            .field("req_client", &self.req_client)
+            .finish()
+    }
+}
+
+impl WebhookClient {
+    pub fn new<S: AsRef<str>>(id: &Snowflake, token: S) -> std::result::Result<WebhookClient, DiscordBuildError> {
+        let req_client = http::build_unauthenticated_request_client("", true)
+            .map(|client| HttpClient::new(client, ClientConfig::default().api_url(), RetryPolicy::default()))
+            .map_err(|e| DiscordBuildError::ReqwestError { err: e })?;
+
+        Ok(WebhookClient::with_http_client(id, token, req_client))
+    }
+
+    /// Takes a webhook url, e.g. `https://discord.com/api/webhooks/{id}/{token}`.
+    pub fn from_url<S: AsRef<str>>(url: S) -> std::result::Result<WebhookClient, DiscordBuildError> {
+        let url = url.as_ref();
+        let (id, token) = parse_webhook_url(url)
+            .ok_or_else(|| DiscordBuildError::InvalidWebhookUrl { url: url.to_string() })?;
+
+        WebhookClient::new(&Snowflake::new(id), token)
+    }
+
+    /// Sends its requests through an existing client, sharing its rate limits.
+    pub fn with_http_client<S: AsRef<str>>(id: &Snowflake, token: S, req_client: HttpClient) -> WebhookClient {
+        WebhookClient {
+            id: id.clone(),
+            token: token.as_ref().to_string(),
+            req_client,
+        }
+    }
+
+    pub fn id(&self) -> &Snowflake {
+        &self.id
+    }
+
+    pub fn req_client(&self) -> HttpClient {
+        self.req_client.clone()
+    }
+
+    /// The webhook, without the user that created it.
+    pub async fn webhook(&self) -> Result<Webhook> {
+        api::get_webhook_with_token(self.req_client(), &self.id, &self.token).await
+    }
+
+    /// The webhook's channel can't be changed with its token.
+    pub async fn modify(&self, webhook: ModifyWebhook, reason: Option<&str>) -> Result<Webhook> {
+        api::modify_webhook_with_token(self.req_client(), &self.id, &self.token, &webhook, reason).await
+    }
+
+    /// Deletes the webhook, the client can't be used anymore after this.
+    pub async fn delete(&self, reason: Option<&str>) -> Result<()> {
+        api::delete_webhook_with_token(self.req_client(), &self.id, &self.token, reason).await
+    }
+
+    /// Sends the message, waiting for discord to confirm it was sent.
+    pub async fn execute<M: Into<ExecuteWebhook>>(&self, message: M) -> Result<Message> {
+        api::execute_webhook(self.req_client(), &self.id, &self.token, message.into(), true).await
+    }
+
+    /// Sends the message without waiting for it to be created, so it isn't returned
+    /// and errors that happen while creating it aren't reported.
+    pub async fn execute_without_wait<M: Into<ExecuteWebhook>>(&self, message: M) -> Result<()> {
+        api::execute_webhook::<serde::de::IgnoredAny>(self.req_client(), &self.id, &self.token, message.into(), false).await
+            .map(|_| ())
+    }
+
+    /// Only messages sent by this webhook can be fetched.
+    /// `thread_id` is needed for messages sent in a thread.
+    pub async fn message(&self, message_id: &Snowflake, thread_id: Option<&Snowflake>) -> Result<Message> {
+        api::get_webhook_message(self.req_client(), &self.id, &self.token, message_id, thread_id).await
+    }
+
+    /// Only messages sent by this webhook can be edited.
+    /// `thread_id` is needed for messages sent in a thread.
+    pub async fn edit_message<M: Into<EditMessage>>(
+        &self,
+        message_id: &Snowflake,
+        message: M,
+        thread_id: Option<&Snowflake>
+    ) -> Result<Message> {
+        api::edit_webhook_message(self.req_client(), &self.id, &self.token, message_id, message.into(), thread_id).await
+    }
+
+    /// Only messages sent by this webhook can be deleted.
+    /// `thread_id` is needed for messages sent in a thread.
+    pub async fn delete_message(&self, message_id: &Snowflake, thread_id: Option<&Snowflake>) -> Result<()> {
+        api::delete_webhook_message(self.req_client(), &self.id, &self.token, message_id, thread_id).await
+    }
+}
+
+/// Splits a webhook url into the webhook's id and token.
+fn parse_webhook_url(url: &str) -> Option<(&str, &str)> {
+    let url = url.split(['?', '#']).next()?;
+    let (_, path) = url.split_once("/webhooks/")?;
+    let mut segments = path.trim_end_matches('/').split('/');
+    let id = segments.next().filter(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))?;
+    let token = segments.next().filter(|token| !token.is_empty())?;
+
+    match segments.next() {
+        None => Some((id, token)),
+        Some(_) => None,
+    }
+}
@@ -0,0 +1,53 @@
+//! Fixtures shared by the gateway compression tests and benchmark.
+// Each file only uses some of them.
+#![allow(dead_code)]
+
+use flate2::{Compress, Compression, FlushCompress};
+
+pub const HELLO: &str = r#"{"t":null,"s":null,"op":10,"d":{"heartbeat_interval":41250,"_trace":["[\"gateway-prd-us-east1-b-0568\",{\"micros\":0.0}]"]}}"#;
+pub const HEARTBEAT_ACK: &str = r#"{"t":null,"s":null,"op":11,"d":null}"#;
+
+/// A GUILD_CREATE payload, large enough to span several messages once compressed.
+pub fn guild_create(channels: usize) -> String {
+    let channels: Vec<String> = (0..channels)
+        .map(|i| format!(r#"{{"id":"{}","type":0,"name":"channel-{i}","position":{i},"topic":null}}"#, 1100000000000000000u64 + i as u64))
+        .collect();
+    format!(r#"{{"t":"GUILD_CREATE","s":2,"op":0,"d":{{"id":"1","channels":[{}]}}}}"#, channels.join(","))
+}
+
+/// Compresses payloads the way the gateway does with zlib-stream, one sync flush per payload.
+pub fn zlib_stream(payloads: &[&str]) -> Vec<Vec<u8>> {
+    let mut deflate = Compress::new(Compression::default(), true);
+    payloads.iter()
+        .map(|payload| {
+            let mut frame = Vec::with_capacity(payload.len() + 64);
+            let mut input = payload.as_bytes();
+            loop {
+                frame.reserve(1024);
+                let total_in = deflate.total_in();
+                deflate.compress_vec(input, &mut frame, FlushCompress::Sync).unwrap();
+                input = &input[(deflate.total_in() - total_in) as usize..];
+                if input.is_empty() && frame.len() < frame.capacity() {
+                    break;
+                }
+            }
+            frame
+        })
+        .collect()
+}
+
+/// Compresses payloads the way the gateway does with zstd-stream, flushing the stream after each payload.
+#[cfg(feature = "zstd")]
+pub fn zstd_stream(payloads: &[&str], window_log: u32) -> Vec<Vec<u8>> {
+    use std::io::Write;
+
+    let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 3).unwrap();
+    encoder.window_log(window_log).unwrap();
+    payloads.iter()
+        .map(|payload| {
+            encoder.write_all(payload.as_bytes()).unwrap();
+            encoder.flush().unwrap();
+            std::mem::take(encoder.get_mut())
+        })
+        .collect()
+}
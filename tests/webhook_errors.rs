@@ -0,0 +1,67 @@
+use discord::http::retry::RetryPolicy;
+use discord::http::{self, HttpClient};
+use discord::model::Snowflake;
+use discord::webhook::WebhookClient;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::time::Duration;
+
+const TOKEN: &str = "super-secret-webhook-token";
+
+/// A client whose requests all fail, nothing listens on port 1.
+fn unreachable_client(retry_policy: RetryPolicy) -> WebhookClient {
+    let client = http::build_unauthenticated_request_client("", false).unwrap();
+    let req_client = HttpClient::new(client, "http://127.0.0.1:1/api/v10".to_string(), retry_policy);
+    WebhookClient::with_http_client(&Snowflake::new("123456789012345678"), TOKEN, req_client)
+}
+
+#[tokio::test]
+async fn failed_requests_dont_leak_the_token() {
+    let webhook = unreachable_client(RetryPolicy::never());
+
+    let err = webhook.execute("hello").await.unwrap_err();
+    assert!(!err.to_string().contains(TOKEN), "{err}");
+    assert!(!format!("{err:?}").contains(TOKEN), "{err:?}");
+}
+
+#[tokio::test]
+async fn retried_requests_dont_leak_the_token() {
+    let policy = RetryPolicy::new()
+        .max_attempts(2)
+        .base_delay(Duration::from_millis(1));
+    let webhook = unreachable_client(policy);
+
+    let err = webhook.webhook().await.unwrap_err();
+    assert!(!err.to_string().contains(TOKEN), "{err}");
+    assert!(!format!("{err:?}").contains(TOKEN), "{err:?}");
+}
+
+#[test]
+fn debug_output_doesnt_leak_the_token() {
+    let webhook = unreachable_client(RetryPolicy::never());
+    assert!(!format!("{webhook:?}").contains(TOKEN));
+}
+
+#[tokio::test]
+async fn tokens_stay_in_their_path_segment() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let len = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..len]);
+        }
+        socket.write_all(b"HTTP/1.1 204 No Content\r\nconnection: close\r\n\r\n").await.unwrap();
+        String::from_utf8_lossy(&request).lines().next().unwrap_or_default().to_string()
+    });
+
+    let client = http::build_unauthenticated_request_client("", false).unwrap();
+    let req_client = HttpClient::new(client, format!("http://{addr}/api/v10"), RetryPolicy::never());
+    let webhook = WebhookClient::with_http_client(&Snowflake::new("123"), "../../users/@me?x=#", req_client);
+
+    webhook.delete(None).await.unwrap();
+    assert_eq!(server.await.unwrap(), "DELETE /api/v10/webhooks/123/%2E%2E%2F%2E%2E%2Fusers%2F%40me%3Fx%3D%23 HTTP/1.1");
+}
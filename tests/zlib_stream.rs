@@ -0,0 +1,108 @@
+mod common;
+
+use common::{guild_create, zlib_stream, HEARTBEAT_ACK, HELLO};
+use discord::gateway::compression::ZlibStreamDecoder;
+
+/// `HELLO` as the first message of a recorded zlib-stream connection.
+const HELLO_FRAME: &[u8] = &[
+    0x78, 0x9c, 0x34, 0xc9, 0x41, 0x0a, 0x83, 0x30, 0x10, 0x05, 0xd0, 0xbb, 0xfc, 0x75, 0x22, 0x49,
+    0xa9, 0xa5, 0xcc, 0x55, 0x8c, 0xc8, 0xa8, 0x43, 0x2b, 0xa4, 0x2a, 0xc9, 0xd8, 0x52, 0x42, 0xee,
+    0x6e, 0x37, 0xdd, 0x3d, 0x78, 0x05, 0x0a, 0x5a, 0x8f, 0x18, 0x0d, 0xf2, 0x1f, 0xdb, 0x0e, 0xf2,
+    0xce, 0x60, 0x06, 0x15, 0x3c, 0x85, 0x93, 0x8e, 0xc2, 0x3a, 0x2c, 0xab, 0x4a, 0x7a, 0x73, 0x04,
+    0x5d, 0xfd, 0xa5, 0xfd, 0xfd, 0xa0, 0x89, 0x27, 0x01, 0x75, 0xe8, 0x02, 0x1e, 0xac, 0xf2, 0xe1,
+    0xaf, 0xdd, 0xd3, 0x6c, 0x8f, 0x6c, 0x85, 0xb3, 0x7a, 0x3b, 0x5a, 0xd7, 0xde, 0xee, 0x01, 0xa6,
+    0x04, 0xbc, 0x96, 0x29, 0x6d, 0x39, 0x80, 0x5c, 0xe3, 0x6a, 0x8f, 0xbe, 0xd6, 0x13, 0x00, 0x00,
+    0xff, 0xff,
+];
+
+/// `HEARTBEAT_ACK` as the second message of the same connection, it can't be inflated on its own.
+const HEARTBEAT_ACK_FRAME: &[u8] = &[
+    0xaa, 0xc6, 0x65, 0xb7, 0x21, 0xd8, 0x6e, 0x90, 0x40, 0x2d, 0x00, 0x00, 0x00, 0xff, 0xff,
+];
+
+#[test]
+fn recorded_frames_share_the_inflate_context() {
+    let mut decoder = ZlibStreamDecoder::new();
+
+    assert_eq!(decoder.push(HELLO_FRAME).unwrap().as_deref(), Some(HELLO));
+    assert_eq!(decoder.push(HEARTBEAT_ACK_FRAME).unwrap().as_deref(), Some(HEARTBEAT_ACK));
+}
+
+#[test]
+fn later_frames_need_the_earlier_ones() {
+    let mut decoder = ZlibStreamDecoder::new();
+
+    assert!(decoder.push(HEARTBEAT_ACK_FRAME).is_err());
+}
+
+#[test]
+fn payload_split_across_messages() {
+    let mut decoder = ZlibStreamDecoder::new();
+    let chunks: Vec<&[u8]> = HELLO_FRAME.chunks(7).collect();
+
+    for chunk in &chunks[..chunks.len() - 1] {
+        assert_eq!(decoder.push(chunk).unwrap(), None);
+    }
+    assert_eq!(decoder.push(chunks[chunks.len() - 1]).unwrap().as_deref(), Some(HELLO));
+    assert_eq!(decoder.push(HEARTBEAT_ACK_FRAME).unwrap().as_deref(), Some(HEARTBEAT_ACK));
+}
+
+#[test]
+fn suffix_split_across_messages() {
+    let mut decoder = ZlibStreamDecoder::new();
+    let (head, tail) = HELLO_FRAME.split_at(HELLO_FRAME.len() - 2);
+
+    assert_eq!(decoder.push(head).unwrap(), None);
+    assert_eq!(decoder.push(tail).unwrap().as_deref(), Some(HELLO));
+}
+
+#[test]
+fn large_payloads() {
+    let ready = guild_create(5000);
+    let payloads = [HELLO, ready.as_str(), HEARTBEAT_ACK, ready.as_str()];
+    let frames = zlib_stream(&payloads);
+    let mut decoder = ZlibStreamDecoder::new();
+
+    for (frame, payload) in frames.iter().zip(payloads) {
+        let mut decoded = None;
+        for chunk in frame.chunks(4096) {
+            assert!(decoded.is_none());
+            decoded = decoder.push(chunk).unwrap();
+        }
+        assert_eq!(decoded.as_deref(), Some(payload));
+    }
+}
+
+#[test]
+fn generated_payload_split_across_messages() {
+    let guild = guild_create(2000);
+    let frames = zlib_stream(&[HELLO, guild.as_str(), HEARTBEAT_ACK]);
+    let mut decoder = ZlibStreamDecoder::new();
+
+    assert_eq!(decoder.push(&frames[0]).unwrap().as_deref(), Some(HELLO));
+
+    // Uneven messages, only the last one ends with the flush suffix.
+    let frame = &frames[1];
+    let cuts = [1, 2, 5, frame.len() / 3, frame.len() / 2, frame.len() - 3, frame.len() - 1, frame.len()];
+    let mut start = 0;
+    for (i, end) in cuts.into_iter().enumerate() {
+        let decoded = decoder.push(&frame[start..end]).unwrap();
+        if i == cuts.len() - 1 {
+            assert_eq!(decoded.as_deref(), Some(guild.as_str()));
+        } else {
+            assert_eq!(decoded, None, "message {i} ended the payload early");
+        }
+        start = end;
+    }
+
+    assert_eq!(decoder.push(&frames[2]).unwrap().as_deref(), Some(HEARTBEAT_ACK));
+}
+
+#[test]
+fn corrupt_frame() {
+    let mut decoder = ZlibStreamDecoder::new();
+    let mut frame = HELLO_FRAME.to_vec();
+    frame[0] = 0x00;
+
+    assert!(decoder.push(&frame).is_err());
+}
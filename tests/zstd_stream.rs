@@ -0,0 +1,35 @@
+#![cfg(feature = "zstd")]
+
+mod common;
+
+use common::{guild_create, zstd_stream, HEARTBEAT_ACK, HELLO};
+use discord::gateway::compression::ZstdStreamDecoder;
+
+#[test]
+fn messages_share_the_decoder_context() {
+    let guild = guild_create(5000);
+    let payloads = [HELLO, guild.as_str(), HEARTBEAT_ACK, guild.as_str()];
+    let messages = zstd_stream(&payloads, 20);
+    let mut decoder = ZstdStreamDecoder::new().unwrap();
+
+    for (message, payload) in messages.iter().zip(payloads) {
+        assert_eq!(decoder.push(message).unwrap(), payload);
+    }
+}
+
+#[test]
+fn later_messages_need_the_earlier_ones() {
+    let messages = zstd_stream(&[HELLO, HEARTBEAT_ACK], 20);
+    let mut decoder = ZstdStreamDecoder::new().unwrap();
+
+    assert!(decoder.push(&messages[1]).is_err());
+}
+
+#[test]
+fn window_is_bounded() {
+    let guild = guild_create(5000);
+    let messages = zstd_stream(&[guild.as_str()], 24);
+    let mut decoder = ZstdStreamDecoder::with_window_log_max(20).unwrap();
+
+    assert!(decoder.push(&messages[0]).is_err());
+}